@@ -1,4 +1,7 @@
 use std::ops::AddAssign;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use slimes::{objective::Optimum, vector::Vector};
 
 pub struct BatchRunData {
     pub min_result: f64,
@@ -46,4 +49,130 @@ impl AddAssign<f64> for BatchRunData {
         self.average = (previous_sum + rhs) / self.run_count as f64;
         
     }
-}
\ No newline at end of file
+}
+
+impl FromIterator<f64> for BatchRunData {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        for result in iter {
+            stats += result;
+        }
+        return stats;
+    }
+}
+
+/// Final value, solution and constraint violation of a single run
+pub type RunResult<V> = (f64, V, f64);
+
+/// Statistics of a batch, with the ones comparing against the optimum left empty when it is not known
+pub struct BatchSummary {
+    pub values: BatchRunData,
+    pub average_error: Option<f64>,
+    /// Empty also when the optimum location is not known
+    pub average_distance: Option<f64>,
+    /// Share of feasible runs with an error of at most the tolerance
+    pub success_rate: Option<f64>,
+    pub feasibility_rate: f64,
+}
+
+pub fn summarize<V: Vector>(results: &[RunResult<V>], optimum: Option<&Optimum<V>>, success_tolerance: f64) -> BatchSummary {
+    let errors = optimum.map(|optimum| results.iter().map(|(value, _, _)| optimum.error(*value)).collect::<Vec<_>>());
+    let average_error = errors.as_ref().map(|errors| errors.iter().copied().collect::<BatchRunData>().average);
+    let average_distance = optimum.and_then(|optimum| {
+        return results.iter().map(|(_, solution, _)| optimum.distance(solution)).collect::<Option<BatchRunData>>();
+    }).map(|distances| distances.average);
+    // infeasible runs never count as successful
+    let success_rate = errors.as_ref().map(|errors| {
+        let successes = errors.iter().zip(results).filter(|&(&error, (_, _, violation))| error <= success_tolerance && *violation == 0.0).count();
+        return successes as f64 / errors.len() as f64;
+    });
+    let feasibility_rate = results.iter().filter(|(_, _, violation)| *violation == 0.0).count() as f64 / results.len() as f64;
+
+    return BatchSummary {
+        values: results.iter().map(|(value, _, _)| *value).collect(),
+        average_error, average_distance, success_rate, feasibility_rate,
+    };
+}
+
+/// Executes exactly `run_count` runs spread over `thread_count` threads.
+/// Results are returned in run order, so aggregating them does not depend on the number of threads.
+/// If any run fails, the error of the first failed run is returned
+pub fn run_batch<T: Send, E: Send>(run_count: usize, thread_count: usize, run: impl Fn(usize) -> Result<T, E> + Sync) -> Result<Vec<T>, E> {
+    let next_run = AtomicUsize::new(0);
+    let mut results = std::thread::scope(|scope| {
+        let threads = (0..thread_count.clamp(1, run_count.max(1))).map(|_| {
+            return scope.spawn(|| {
+                let mut thread_results = Vec::new();
+                loop {
+                    let run_index = next_run.fetch_add(1, Ordering::Relaxed);
+                    if run_index >= run_count {
+                        break;
+                    }
+                    thread_results.push((run_index, run(run_index)));
+                }
+                return thread_results;
+            });
+        }).collect::<Vec<_>>();
+        return threads.into_iter().flat_map(|handle| handle.join().unwrap()).collect::<Vec<_>>();
+    });
+    results.sort_unstable_by_key(|(run_index, _)| *run_index);
+
+    return results.into_iter().map(|(_, result)| result).collect();
+}
+
+#[cfg(test)]
+mod test {
+    use slimes::{objective::Optimum, vector::VectorN};
+
+    use crate::batch::{run_batch, summarize, BatchRunData};
+
+    #[test]
+    fn thread_count_independence_test() {
+        let run = |run_index: usize| Ok::<_, ()>((run_index as f64 * 0.1).sin());
+        let single = run_batch(100, 1, run).unwrap().into_iter().collect::<BatchRunData>();
+        let multiple = run_batch(100, 64, run).unwrap().into_iter().collect::<BatchRunData>();
+
+        assert_eq!(single.run_count, 100);
+        assert_eq!(multiple.run_count, 100);
+        assert_eq!(single.average.to_bits(), multiple.average.to_bits());
+        assert_eq!(single.min_result, multiple.min_result);
+        assert_eq!(single.max_result, multiple.max_result);
+    }
+
+    #[test]
+    fn summary_test() {
+        let results = [
+            (1.0, VectorN::new([1.0, 0.0]), 0.0),
+            (1.5, VectorN::new([0.0, 2.0]), 0.0),
+            (1.0, VectorN::new([0.0, 0.0]), 0.5),
+            (4.0, VectorN::new([3.0, 4.0]), 0.0),
+        ];
+        let optimum = Optimum { value: 1.0, location: Some(VectorN::new([0.0, 0.0])) };
+        let summary = summarize(&results, Some(&optimum), 0.5);
+        assert_eq!(summary.values.run_count, 4);
+        assert_eq!(summary.values.max_result, 4.0);
+        assert_eq!(summary.average_error, Some(0.875));
+        assert_eq!(summary.average_distance, Some(2.0));
+        // the third run is within the tolerance, but infeasible
+        assert_eq!(summary.success_rate, Some(0.5));
+        assert_eq!(summary.feasibility_rate, 0.75);
+
+        let without_location = Optimum { value: 1.0, location: None };
+        let summary = summarize(&results, Some(&without_location), 0.5);
+        assert_eq!(summary.average_distance, None);
+        assert_eq!(summary.success_rate, Some(0.5));
+
+        let summary = summarize(&results, None, 0.5);
+        assert_eq!(summary.average_error, None);
+        assert_eq!(summary.average_distance, None);
+        assert_eq!(summary.success_rate, None);
+        assert_eq!(summary.feasibility_rate, 0.75);
+        assert_eq!(summary.values.average, 1.875);
+    }
+
+    #[test]
+    fn first_error_test() {
+        let result = run_batch(20, 4, |run_index| if run_index % 7 == 3 { Err(run_index) } else { Ok(run_index) });
+        assert_eq!(result.err(), Some(3));
+    }
+}
@@ -0,0 +1,304 @@
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, PI};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{bounds::Bounds, error::SlimeError, objective::{Objective, Optimum}, vector::Vector};
+
+/// Range of the continuous positions behind the bits, the transfer functions are saturated at its ends
+pub const POSITION_LIMIT: f64 = 6.0;
+
+/// Largest dynamic programming table used to find the optimum of a knapsack instance
+const MAX_KNAPSACK_TABLE: usize = 10_000_000;
+
+/// Maps a continuous coordinate to the probability of a bit, following Mirjalili and Lewis.
+/// S-shaped functions give the probability of the bit being set, V-shaped ones the probability of flipping it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferFunction {
+	S1,
+	/// The sigmoid of the original binary particle swarm
+	#[default]
+	S2,
+	S3,
+	S4,
+	V1,
+	V2,
+	V3,
+	V4,
+}
+
+impl TransferFunction {
+	pub const ALL: [TransferFunction; 8] = [Self::S1, Self::S2, Self::S3, Self::S4, Self::V1, Self::V2, Self::V3, Self::V4];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::S1 => return "s1",
+			Self::S2 => return "s2",
+			Self::S3 => return "s3",
+			Self::S4 => return "s4",
+			Self::V1 => return "v1",
+			Self::V2 => return "v2",
+			Self::V3 => return "v3",
+			Self::V4 => return "v4",
+		}
+	}
+
+	pub fn is_v_shaped(self) -> bool {
+		return matches!(self, Self::V1 | Self::V2 | Self::V3 | Self::V4);
+	}
+
+	pub fn probability(self, x: f64) -> f64 {
+		match self {
+			Self::S1 => return 1.0 / (1.0 + (-2.0 * x).exp()),
+			Self::S2 => return 1.0 / (1.0 + (-x).exp()),
+			Self::S3 => return 1.0 / (1.0 + (-x / 2.0).exp()),
+			Self::S4 => return 1.0 / (1.0 + (-x / 3.0).exp()),
+			Self::V1 => return erf(PI.sqrt() / 2.0 * x).abs(),
+			Self::V2 => return x.tanh().abs(),
+			Self::V3 => return (x / (1.0 + x.powi(2)).sqrt()).abs(),
+			Self::V4 => return (FRAC_2_PI * (FRAC_PI_2 * x).atan()).abs(),
+		}
+	}
+
+	/// Updates the bits, stored as zeros and ones, from the continuous position
+	pub fn update<V: Vector>(self, bits: &mut V, position: &V, random_source: &mut impl Rng) {
+		for (bit, &x) in bits.as_mut_slice().iter_mut().zip(position.as_slice()) {
+			let probability = self.probability(x);
+			if self.is_v_shaped() {
+				if random_source.gen::<f64>() < probability {
+					*bit = 1.0 - *bit;
+				}
+			} else {
+				*bit = if random_source.gen::<f64>() < probability { 1.0 } else { 0.0 };
+			}
+		}
+	}
+}
+
+/// Error function by the approximation 7.1.26 of Abramowitz and Stegun, accurate to 1.5e-7
+fn erf(x: f64) -> f64 {
+	let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+	let polynomial = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+	return (1.0 - polynomial * (-x * x).exp()).copysign(x);
+}
+
+impl FromStr for TransferFunction {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|function| function.name() == name).ok_or_else(|| {
+			return format!("unknown transfer function `{}`, available functions are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for TransferFunction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", self.name());
+	}
+}
+
+/// Function of a fixed number of bits, minimized unless the fitness settings say otherwise
+pub trait BinaryObjective: Send + Sync {
+	fn value(&self, bits: &[bool]) -> f64;
+
+	fn bit_count(&self) -> usize;
+
+	/// Name used when reporting results
+	fn name(&self) -> Option<&str> {
+		return None;
+	}
+
+	/// Best reachable value, if known
+	fn optimum(&self) -> Option<f64> {
+		return None;
+	}
+
+	/// Values of the inequality constraints `g(x) <= 0`
+	fn inequality_constraints(&self, _bits: &[bool]) -> Vec<f64> {
+		return Vec::new();
+	}
+}
+
+/// Binary objective evaluated on vectors of zeros and ones, so the continuous optimizers and their settings can be reused.
+/// Coordinates of at least one half are set bits
+pub struct Binarized<V: Vector> {
+	inner: Arc<dyn BinaryObjective>,
+	vector: PhantomData<fn(&V)>,
+}
+
+impl<V: Vector> Binarized<V> {
+	pub fn new(inner: Arc<dyn BinaryObjective>) -> Self {
+		return Self {
+			inner,
+			vector: PhantomData,
+		};
+	}
+
+	pub fn bits(input: &V) -> Vec<bool> {
+		return input.as_slice().iter().map(|&a| a >= 0.5).collect();
+	}
+}
+
+impl<V: Vector> Objective<V> for Binarized<V> {
+	fn value(&self, input: &V) -> f64 {
+		return self.inner.value(&Self::bits(input));
+	}
+
+	fn bounds(&self, _dimensions: usize) -> Bounds<V> {
+		return Bounds::uniform(self.inner.bit_count(), 0.0, 1.0);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.inner.name();
+	}
+
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum().map(|value| Optimum { value, location: None });
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.inequality_constraints(&Self::bits(input));
+	}
+}
+
+/// 0/1 knapsack problem, the total value of the packed items is maximized with their total weight limited by the capacity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Knapsack {
+	values: Vec<f64>,
+	weights: Vec<f64>,
+	capacity: f64,
+	optimum: Option<f64>,
+}
+
+impl Knapsack {
+	/// The optimum is computed by dynamic programming when all weights and the capacity are small integers
+	pub fn new(values: Vec<f64>, weights: Vec<f64>, capacity: f64) -> Result<Self, SlimeError> {
+		if values.is_empty() || values.len() != weights.len() {
+			return Err(SlimeError::InvalidBenchmarkData(format!("knapsack has {} values and {} weights", values.len(), weights.len())));
+		}
+		if !values.iter().chain(&weights).chain([&capacity]).all(|a| a.is_finite() && *a >= 0.0) {
+			return Err(SlimeError::InvalidBenchmarkData("knapsack values, weights and capacity must be non-negative".to_string()));
+		}
+		let mut result = Self { values, weights, capacity, optimum: None };
+		result.optimum = result.solve();
+		return Ok(result);
+	}
+
+	/// Uncorrelated instance with values and weights between 1 and 100, the capacity is half of the total weight
+	pub fn random(items: usize, random_source: &mut impl Rng) -> Self {
+		let values = (0..items).map(|_| random_source.gen_range(1..=100) as f64).collect::<Vec<_>>();
+		let weights = (0..items).map(|_| random_source.gen_range(1..=100) as f64).collect::<Vec<_>>();
+		let capacity = (weights.iter().sum::<f64>() / 2.0).floor();
+		return Self::new(values, weights, capacity).unwrap();
+	}
+
+	pub fn capacity(&self) -> f64 {
+		return self.capacity;
+	}
+
+	pub fn total_weight(&self, bits: &[bool]) -> f64 {
+		return self.weights.iter().zip(bits).filter(|(_, &bit)| bit).map(|(weight, _)| weight).sum();
+	}
+
+	fn solve(&self) -> Option<f64> {
+		let is_integer = |a: &f64| a.fract() == 0.0;
+		if !self.weights.iter().all(is_integer) || !is_integer(&self.capacity) || self.values.len() * (self.capacity as usize + 1) > MAX_KNAPSACK_TABLE {
+			return None;
+		}
+		// best value for every remaining capacity
+		let mut best = vec![0.0f64; self.capacity as usize + 1];
+		for (value, &weight) in self.values.iter().zip(&self.weights) {
+			let weight = weight as usize;
+			for remaining in (weight..best.len()).rev() {
+				best[remaining] = best[remaining].max(best[remaining - weight] + value);
+			}
+		}
+		return best.last().copied();
+	}
+}
+
+impl BinaryObjective for Knapsack {
+	fn value(&self, bits: &[bool]) -> f64 {
+		return self.values.iter().zip(bits).filter(|(_, &bit)| bit).map(|(value, _)| value).sum();
+	}
+
+	fn bit_count(&self) -> usize {
+		return self.values.len();
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some("knapsack");
+	}
+
+	fn optimum(&self) -> Option<f64> {
+		return self.optimum;
+	}
+
+	fn inequality_constraints(&self, bits: &[bool]) -> Vec<f64> {
+		return vec![self.total_weight(bits) - self.capacity];
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{binary::{Binarized, BinaryObjective, Knapsack, TransferFunction, POSITION_LIMIT}, fitness::{Direction, FitnessSettings}, multi_swarm::MultiSwarm, optimizer::Optimizer, random::RandomSource, slime, vector::{DynVector, Vector, VectorN}};
+
+	#[test]
+	fn transfer_functions_test() {
+		for function in TransferFunction::ALL {
+			let at_limit = function.probability(POSITION_LIMIT);
+			assert!(at_limit > 0.85 && at_limit <= 1.0, "{} is {} at the limit", function, at_limit);
+			if function.is_v_shaped() {
+				assert!(function.probability(0.0).abs() < 1e-6);
+				assert_eq!(function.probability(-2.0), function.probability(2.0));
+			} else {
+				assert_eq!(function.probability(0.0), 0.5);
+				assert!((function.probability(-2.0) + function.probability(2.0) - 1.0).abs() < 1e-12);
+			}
+		}
+
+		let mut random_source = RandomSource::seed_from_u64(0);
+		let mut bits = VectorN::new([0.0, 1.0, 0.0, 1.0]);
+		// flips nothing at zero and everything far from it
+		TransferFunction::V2.update(&mut bits, &VectorN::new([0.0, 0.0, 100.0, -100.0]), &mut random_source);
+		assert_eq!(bits, VectorN::new([0.0, 1.0, 1.0, 0.0]));
+		TransferFunction::S2.update(&mut bits, &VectorN::new([100.0, -100.0, 100.0, -100.0]), &mut random_source);
+		assert_eq!(bits, VectorN::new([1.0, 0.0, 1.0, 0.0]));
+	}
+
+	#[test]
+	fn knapsack_test() {
+		let knapsack = Knapsack::new(vec![60.0, 100.0, 120.0], vec![10.0, 20.0, 30.0], 50.0).unwrap();
+		assert_eq!(knapsack.optimum(), Some(220.0));
+		assert_eq!(knapsack.value(&[false, true, true]), 220.0);
+		assert_eq!(knapsack.inequality_constraints(&[true, true, true]), vec![10.0]);
+		assert!(Knapsack::new(vec![1.0], vec![1.0, 2.0], 1.0).is_err());
+		assert!(Knapsack::new(vec![1.0], vec![1.0], 1.5).unwrap().optimum().is_none());
+	}
+
+	#[test]
+	fn binary_slime_test() {
+		let knapsack = Arc::new(Knapsack::random(30, &mut RandomSource::seed_from_u64(0)));
+		let optimum = knapsack.optimum().unwrap();
+		let settings = FitnessSettings::new().with_direction(Direction::Maximize);
+		for transfer in [TransferFunction::S2, TransferFunction::V2] {
+			let mut world = MultiSwarm::<DynVector, _>::new(3, 0.1, RandomSource::seed_from_u64(0), |rng_source| {
+				return slime::WorldState::binary(20, knapsack.clone(), 100, 0.03, transfer, settings, rng_source);
+			}).unwrap();
+			let result = world.run().unwrap();
+			let bits = Binarized::bits(&result.best_solution);
+			assert!(result.best_solution.as_slice().iter().all(|&bit| bit == 0.0 || bit == 1.0));
+			assert_eq!(result.best_violation, 0.0, "{} ended over the capacity", transfer);
+			assert_eq!(result.best_value, knapsack.value(&bits));
+			assert!(result.best_value > 0.8 * optimum, "{} found {} of {}", transfer, result.best_value, optimum);
+		}
+	}
+}
@@ -0,0 +1,73 @@
+use rand::Rng;
+
+use crate::vector::Vector;
+
+/// Box constraints of the search space, with separate lower and upper bound for every coordinate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounds<V: Vector> {
+	pub lower: V,
+	pub upper: V,
+}
+
+impl<V: Vector> Bounds<V> {
+	pub fn new(lower: V, upper: V) -> Self {
+		return Self {
+			lower, upper
+		};
+	}
+
+	/// Bounds with the same range for every coordinate
+	pub fn uniform(dimensions: usize, lower: f64, upper: f64) -> Self {
+		let mut result = Self::new(V::zeros(dimensions), V::zeros(dimensions));
+		result.lower.as_mut_slice().fill(lower);
+		result.upper.as_mut_slice().fill(upper);
+		return result;
+	}
+
+	pub fn dimensions(&self) -> usize {
+		return self.lower.dimensions();
+	}
+
+	/// Whether the bounds are not empty and every lower bound is strictly smaller than the matching upper bound
+	pub fn is_valid(&self) -> bool {
+		return self.dimensions() > 0 && self.lower.dimensions() == self.upper.dimensions() &&
+			self.lower.as_slice().iter().zip(self.upper.as_slice()).all(|(lower, upper)| lower < upper);
+	}
+
+	pub fn contains(&self, point: &V) -> bool {
+		return point.as_slice().iter().enumerate().all(|(index, a)| {
+			return (self.lower.as_slice()[index]..=self.upper.as_slice()[index]).contains(a);
+		});
+	}
+
+	/// Picks a point uniformly distributed inside the bounds
+	pub fn sample(&self, random_source: &mut impl Rng) -> V {
+		let mut result = self.lower.clone();
+		for (coordinate, upper) in result.as_mut_slice().iter_mut().zip(self.upper.as_slice()) {
+			*coordinate = random_source.gen_range(*coordinate..*upper);
+		}
+		return result;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use rand::thread_rng;
+
+	use crate::{bounds::Bounds, vector::VectorN};
+
+	#[test]
+	fn sample_test() {
+		let bounds = Bounds::new(VectorN::new([-1.0, 10.0, 0.0]), VectorN::new([1.0, 20.0, 0.001]));
+		assert!(bounds.is_valid());
+		for _ in 0..100 {
+			assert!(bounds.contains(&bounds.sample(&mut thread_rng())));
+		}
+	}
+
+	#[test]
+	fn invalid_test() {
+		let bounds = Bounds::new(VectorN::new([-1.0, 10.0]), VectorN::new([1.0, 10.0]));
+		assert!(!bounds.is_valid());
+	}
+}
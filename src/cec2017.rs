@@ -0,0 +1,570 @@
+use std::f64::consts::{E, PI, TAU};
+use std::path::Path;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{bounds::Bounds, error::SlimeError, objective::{Objective, Optimum}, random, transform::random_rotation, vector::Vector};
+
+/// Dimension counts for which the suite is defined
+pub const DIMENSIONS: [usize; 6] = [2, 10, 20, 30, 50, 100];
+
+/// Numbers of the functions in the suite, the second one was excluded by the organizers
+pub const FUNCTION_NUMBERS: [usize; 29] = [1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30];
+
+/// Seed of the generated shift vectors, rotation matrices and shuffles
+pub const DEFAULT_SEED: u64 = 2017;
+
+const SEARCH_RANGE: f64 = 100.0;
+// generated optima keep a margin to the bounds, like the official ones
+const SHIFT_RANGE: f64 = 80.0;
+// weight of a composition component whose optimum is hit exactly
+const INFINITE_WEIGHT: f64 = 1e99;
+
+/// Basic functions the suite is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Basic {
+	BentCigar,
+	Zakharov,
+	Rosenbrock,
+	Rastrigin,
+	ExpandedSchafferF6,
+	LunacekBiRastrigin,
+	NonContinuousRastrigin,
+	Levy,
+	Schwefel,
+	Elliptic,
+	Ackley,
+	SchafferF7,
+	HgBat,
+	HappyCat,
+	Katsuura,
+	GriewankRosenbrock,
+	Weierstrass,
+	Griewank,
+	Discus,
+}
+
+/// Part of a composition function, weighted by its lambda and delta
+#[derive(Debug, Clone, Copy)]
+enum Part {
+	Basic(Basic),
+	Hybrid(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Definition {
+	Simple(Basic),
+	/// Functions applied to consecutive parts of the shuffled point, with their share of the coordinates
+	Hybrid(&'static [(Basic, f64)]),
+	/// Parts with their lambda and delta
+	Composition(&'static [(Part, f64, f64)]),
+}
+
+fn definition(number: usize) -> Option<Definition> {
+	use Basic::*;
+
+	match number {
+		1 => return Some(Definition::Simple(BentCigar)),
+		3 => return Some(Definition::Simple(Zakharov)),
+		4 => return Some(Definition::Simple(Rosenbrock)),
+		5 => return Some(Definition::Simple(Rastrigin)),
+		// the reference implementation uses Schaffer's F7 here, while the report names the expanded F6
+		6 => return Some(Definition::Simple(SchafferF7)),
+		7 => return Some(Definition::Simple(LunacekBiRastrigin)),
+		8 => return Some(Definition::Simple(NonContinuousRastrigin)),
+		9 => return Some(Definition::Simple(Levy)),
+		10 => return Some(Definition::Simple(Schwefel)),
+		11 => return Some(Definition::Hybrid(&[(Zakharov, 0.2), (Rosenbrock, 0.4), (Rastrigin, 0.4)])),
+		12 => return Some(Definition::Hybrid(&[(Elliptic, 0.3), (Schwefel, 0.3), (BentCigar, 0.4)])),
+		13 => return Some(Definition::Hybrid(&[(BentCigar, 0.3), (Rosenbrock, 0.3), (LunacekBiRastrigin, 0.4)])),
+		14 => return Some(Definition::Hybrid(&[(Elliptic, 0.2), (Ackley, 0.2), (SchafferF7, 0.2), (Rastrigin, 0.4)])),
+		15 => return Some(Definition::Hybrid(&[(BentCigar, 0.2), (HgBat, 0.2), (Rastrigin, 0.3), (Rosenbrock, 0.3)])),
+		16 => return Some(Definition::Hybrid(&[(ExpandedSchafferF6, 0.2), (HgBat, 0.2), (Rosenbrock, 0.3), (Schwefel, 0.3)])),
+		17 => return Some(Definition::Hybrid(&[(Katsuura, 0.1), (Ackley, 0.2), (GriewankRosenbrock, 0.2), (Schwefel, 0.2), (Rastrigin, 0.3)])),
+		18 => return Some(Definition::Hybrid(&[(Elliptic, 0.2), (Ackley, 0.2), (Rastrigin, 0.2), (HgBat, 0.2), (Discus, 0.2)])),
+		19 => return Some(Definition::Hybrid(&[(BentCigar, 0.2), (Rastrigin, 0.2), (GriewankRosenbrock, 0.2), (Weierstrass, 0.2), (ExpandedSchafferF6, 0.2)])),
+		20 => return Some(Definition::Hybrid(&[(HappyCat, 0.1), (Katsuura, 0.1), (Ackley, 0.2), (Rastrigin, 0.2), (Schwefel, 0.2), (SchafferF7, 0.2)])),
+		21 => return Some(Definition::Composition(&[
+			(Part::Basic(Rosenbrock), 1.0, 10.0), (Part::Basic(Elliptic), 1e-6, 20.0), (Part::Basic(Rastrigin), 1.0, 30.0),
+		])),
+		22 => return Some(Definition::Composition(&[
+			(Part::Basic(Rastrigin), 1.0, 10.0), (Part::Basic(Griewank), 10.0, 20.0), (Part::Basic(Schwefel), 1.0, 30.0),
+		])),
+		23 => return Some(Definition::Composition(&[
+			(Part::Basic(Rosenbrock), 1.0, 10.0), (Part::Basic(Ackley), 10.0, 20.0), (Part::Basic(Schwefel), 1.0, 30.0), (Part::Basic(Rastrigin), 1.0, 40.0),
+		])),
+		24 => return Some(Definition::Composition(&[
+			(Part::Basic(Ackley), 10.0, 10.0), (Part::Basic(Elliptic), 1e-6, 20.0), (Part::Basic(Griewank), 10.0, 30.0), (Part::Basic(Rastrigin), 1.0, 40.0),
+		])),
+		25 => return Some(Definition::Composition(&[
+			(Part::Basic(Rastrigin), 10.0, 10.0), (Part::Basic(HappyCat), 1.0, 20.0), (Part::Basic(Ackley), 10.0, 30.0),
+			(Part::Basic(Discus), 1e-6, 40.0), (Part::Basic(Rosenbrock), 1.0, 50.0),
+		])),
+		26 => return Some(Definition::Composition(&[
+			(Part::Basic(ExpandedSchafferF6), 5e-4, 10.0), (Part::Basic(Schwefel), 1.0, 20.0), (Part::Basic(Griewank), 10.0, 20.0),
+			(Part::Basic(Rosenbrock), 1.0, 30.0), (Part::Basic(Rastrigin), 10.0, 40.0),
+		])),
+		27 => return Some(Definition::Composition(&[
+			(Part::Basic(HgBat), 10.0, 10.0), (Part::Basic(Rastrigin), 10.0, 20.0), (Part::Basic(Schwefel), 2.5, 30.0),
+			(Part::Basic(BentCigar), 1e-26, 40.0), (Part::Basic(Elliptic), 1e-6, 50.0), (Part::Basic(ExpandedSchafferF6), 5e-4, 60.0),
+		])),
+		28 => return Some(Definition::Composition(&[
+			(Part::Basic(Ackley), 10.0, 10.0), (Part::Basic(Griewank), 10.0, 20.0), (Part::Basic(Discus), 1e-6, 30.0),
+			(Part::Basic(Rosenbrock), 1.0, 40.0), (Part::Basic(HappyCat), 1.0, 50.0), (Part::Basic(ExpandedSchafferF6), 5e-4, 60.0),
+		])),
+		29 => return Some(Definition::Composition(&[(Part::Hybrid(15), 1.0, 10.0), (Part::Hybrid(16), 1.0, 30.0), (Part::Hybrid(17), 1.0, 50.0)])),
+		30 => return Some(Definition::Composition(&[(Part::Hybrid(15), 1.0, 10.0), (Part::Hybrid(18), 1.0, 30.0), (Part::Hybrid(19), 1.0, 50.0)])),
+		_ => return None,
+	}
+}
+
+impl Definition {
+	fn component_count(self) -> usize {
+		match self {
+			Self::Simple(_) | Self::Hybrid(_) => return 1,
+			Self::Composition(parts) => return parts.len(),
+		}
+	}
+
+	fn is_hybrid(self) -> bool {
+		match self {
+			Self::Simple(_) => return false,
+			Self::Hybrid(_) => return true,
+			Self::Composition(parts) => return parts.iter().any(|(part, _, _)| matches!(part, Part::Hybrid(_))),
+		}
+	}
+}
+
+/// Shifts, scales and rotates the point like `sr_func` of the reference implementation
+fn shift_rotate(x: &[f64], shift: &[f64], rotation: &[f64], scale: f64, shift_flag: bool, rotate_flag: bool) -> Vec<f64> {
+	let shifted = x.iter().enumerate().map(|(index, a)| {
+		return if shift_flag { (a - shift[index]) * scale } else { a * scale };
+	}).collect::<Vec<_>>();
+	if !rotate_flag {
+		return shifted;
+	}
+	let n = x.len();
+	return (0..n).map(|row| {
+		return rotation[row * n..(row + 1) * n].iter().zip(&shifted).map(|(m, a)| m * a).sum::<f64>();
+	}).collect();
+}
+
+fn rastrigin(z: &[f64]) -> f64 {
+	return z.iter().map(|a| a.powi(2) - 10.0 * (TAU * a).cos() + 10.0).sum();
+}
+
+fn schaffer_pair(x: f64, y: f64) -> f64 {
+	let squares = x.powi(2) + y.powi(2);
+	return 0.5 + (squares.sqrt().sin().powi(2) - 0.5) / (1.0 + 0.001 * squares).powi(2);
+}
+
+impl Basic {
+	fn scale(self) -> f64 {
+		match self {
+			Self::Rosenbrock => return 2.048 / 100.0,
+			Self::Rastrigin | Self::NonContinuousRastrigin => return 5.12 / 100.0,
+			Self::LunacekBiRastrigin => return 10.0 / 100.0,
+			Self::Schwefel => return 1000.0 / 100.0,
+			Self::HgBat | Self::HappyCat | Self::Katsuura | Self::GriewankRosenbrock => return 5.0 / 100.0,
+			Self::Weierstrass => return 0.5 / 100.0,
+			Self::Griewank => return 600.0 / 100.0,
+			_ => return 1.0,
+		}
+	}
+
+	fn evaluate(self, x: &[f64], shift: &[f64], rotation: &[f64], shift_flag: bool, rotate_flag: bool) -> f64 {
+		let n = x.len();
+		let n_f64 = n as f64;
+		match self {
+			Self::LunacekBiRastrigin => return bi_rastrigin(x, shift, rotation, shift_flag, rotate_flag),
+			Self::NonContinuousRastrigin => {
+				let rounded = x.iter().zip(shift).map(|(a, o)| {
+					return if (a - o).abs() > 0.5 { o + (2.0 * (a - o) + 0.5).floor() / 2.0 } else { *a };
+				}).collect::<Vec<_>>();
+				return rastrigin(&shift_rotate(&rounded, shift, rotation, self.scale(), shift_flag, rotate_flag));
+			}
+			_ => {}
+		}
+
+		let mut z = shift_rotate(x, shift, rotation, self.scale(), shift_flag, rotate_flag);
+		match self {
+			Self::BentCigar => return z[0].powi(2) + 1e6 * z[1..].iter().map(|a| a.powi(2)).sum::<f64>(),
+			Self::Zakharov => {
+				let weighted_sum = z.iter().enumerate().map(|(index, a)| 0.5 * (index + 1) as f64 * a).sum::<f64>();
+				return z.iter().map(|a| a.powi(2)).sum::<f64>() + weighted_sum.powi(2) + weighted_sum.powi(4);
+			}
+			Self::Rosenbrock => {
+				z.iter_mut().for_each(|a| *a += 1.0);
+				return z.windows(2).map(|pair| 100.0 * (pair[0].powi(2) - pair[1]).powi(2) + (pair[0] - 1.0).powi(2)).sum();
+			}
+			Self::Rastrigin | Self::NonContinuousRastrigin => return rastrigin(&z),
+			Self::ExpandedSchafferF6 => return (0..n).map(|index| schaffer_pair(z[index], z[(index + 1) % n])).sum(),
+			Self::Levy => {
+				let w = z.iter().map(|a| 1.0 + a / 4.0).collect::<Vec<_>>();
+				let last = w[n - 1];
+				let middle = w[..n - 1].iter().map(|w_i| (w_i - 1.0).powi(2) * (1.0 + 10.0 * (PI * w_i + 1.0).sin().powi(2))).sum::<f64>();
+				return (PI * w[0]).sin().powi(2) + middle + (last - 1.0).powi(2) * (1.0 + (TAU * last).sin().powi(2));
+			}
+			Self::Schwefel => {
+				let sum = z.iter().map(|a| {
+					let a = a + 4.209687462275036e2;
+					if a > 500.0 {
+						let folded = 500.0 - a % 500.0;
+						return -folded * folded.sqrt().sin() + ((a - 500.0) / 100.0).powi(2) / n_f64;
+					}
+					if a < -500.0 {
+						let folded = -500.0 + a.abs() % 500.0;
+						return -folded * (500.0 - a.abs() % 500.0).sqrt().sin() + ((a + 500.0) / 100.0).powi(2) / n_f64;
+					}
+					return -a * a.abs().sqrt().sin();
+				}).sum::<f64>();
+				return sum + 4.189828872724338e2 * n_f64;
+			}
+			Self::Elliptic => {
+				let exponent_step = if n > 1 { 6.0 / (n - 1) as f64 } else { 0.0 };
+				return z.iter().enumerate().map(|(index, a)| 10f64.powf(exponent_step * index as f64) * a.powi(2)).sum();
+			}
+			Self::Ackley => {
+				let squares = z.iter().map(|a| a.powi(2)).sum::<f64>() / n_f64;
+				let cosines = z.iter().map(|a| (TAU * a).cos()).sum::<f64>() / n_f64;
+				return -20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + E;
+			}
+			Self::SchafferF7 => {
+				let sum = z.windows(2).map(|pair| {
+					let s = (pair[0].powi(2) + pair[1].powi(2)).sqrt();
+					return s.sqrt() + s.sqrt() * (50.0 * s.powf(0.2)).sin().powi(2);
+				}).sum::<f64>();
+				let pair_count = (n - 1).max(1) as f64;
+				return sum.powi(2) / pair_count / pair_count;
+			}
+			Self::HgBat | Self::HappyCat => {
+				z.iter_mut().for_each(|a| *a -= 1.0);
+				let squared_norm = z.iter().map(|a| a.powi(2)).sum::<f64>();
+				let sum = z.iter().sum::<f64>();
+				let first = if self == Self::HgBat {
+					(squared_norm.powi(2) - sum.powi(2)).abs().sqrt()
+				} else {
+					(squared_norm - n_f64).abs().powf(0.25)
+				};
+				return first + (0.5 * squared_norm + sum) / n_f64 + 0.5;
+			}
+			Self::Katsuura => {
+				let exponent = 10.0 / n_f64.powf(1.2);
+				let product = z.iter().enumerate().map(|(index, a)| {
+					let sum = (1..=32).map(|j| {
+						let power = 2f64.powi(j);
+						return (power * a - (power * a + 0.5).floor()).abs() / power;
+					}).sum::<f64>();
+					return (1.0 + (index + 1) as f64 * sum).powf(exponent);
+				}).product::<f64>();
+				let factor = 10.0 / n_f64.powi(2);
+				return product * factor - factor;
+			}
+			Self::GriewankRosenbrock => {
+				z.iter_mut().for_each(|a| *a += 1.0);
+				return (0..n).map(|index| {
+					let (a, next) = (z[index], z[(index + 1) % n]);
+					let rosenbrock = 100.0 * (a.powi(2) - next).powi(2) + (a - 1.0).powi(2);
+					return rosenbrock.powi(2) / 4000.0 - rosenbrock.cos() + 1.0;
+				}).sum();
+			}
+			Self::Weierstrass => {
+				let terms = (0..=20).map(|k| (0.5f64.powi(k), 3f64.powi(k))).collect::<Vec<_>>();
+				let offset = terms.iter().map(|(a, b)| a * (PI * b).cos()).sum::<f64>();
+				return z.iter().map(|x_i| {
+					return terms.iter().map(|(a, b)| a * (TAU * b * (x_i + 0.5)).cos()).sum::<f64>();
+				}).sum::<f64>() - n_f64 * offset;
+			}
+			Self::Griewank => {
+				let sum = z.iter().map(|a| a.powi(2)).sum::<f64>() / 4000.0;
+				let product = z.iter().enumerate().map(|(index, a)| (a / ((index + 1) as f64).sqrt()).cos()).product::<f64>();
+				return sum - product + 1.0;
+			}
+			Self::Discus => return 1e6 * z[0].powi(2) + z[1..].iter().map(|a| a.powi(2)).sum::<f64>(),
+			Self::LunacekBiRastrigin => unreachable!(),
+		}
+	}
+}
+
+fn bi_rastrigin(x: &[f64], shift: &[f64], rotation: &[f64], shift_flag: bool, rotate_flag: bool) -> f64 {
+	let n = x.len() as f64;
+	let mu0 = 2.5;
+	let d = 1.0;
+	let s = 1.0 - 1.0 / (2.0 * (n + 20.0).sqrt() - 8.2);
+	let mu1 = -((mu0 * mu0 - d) / s).sqrt();
+
+	// the sign of the shift decides which of the two funnels is the global one
+	let z = shift_rotate(x, shift, rotation, Basic::LunacekBiRastrigin.scale(), shift_flag, false).iter().zip(shift).map(|(a, o)| {
+		return if *o < 0.0 { -2.0 * a } else { 2.0 * a };
+	}).collect::<Vec<_>>();
+	let first = z.iter().map(|a| a.powi(2)).sum::<f64>();
+	let second = z.iter().map(|a| (a + mu0 - mu1).powi(2)).sum::<f64>() * s + d * n;
+	let rotated = shift_rotate(&z, &[], rotation, 1.0, false, rotate_flag);
+	let cosines = rotated.iter().map(|a| (TAU * a).cos()).sum::<f64>();
+	return first.min(second) + 10.0 * (n - cosines);
+}
+
+/// Sizes of the parts of a hybrid function, computed like the reference implementation
+fn part_sizes(parts: &[(Basic, f64)], dimensions: usize) -> Vec<usize> {
+	let mut sizes = parts[..parts.len() - 1].iter().map(|(_, share)| (share * dimensions as f64).ceil() as usize).collect::<Vec<_>>();
+	sizes.push(dimensions - sizes.iter().sum::<usize>());
+	return sizes;
+}
+
+fn hybrid(parts: &[(Basic, f64)], x: &[f64], shift: &[f64], rotation: &[f64], shuffle: &[usize]) -> f64 {
+	let z = shift_rotate(x, shift, rotation, 1.0, true, true);
+	let shuffled = shuffle.iter().map(|&index| z[index]).collect::<Vec<_>>();
+	let mut start = 0;
+	return parts.iter().zip(part_sizes(parts, x.len())).map(|((basic, _), size)| {
+		let value = basic.evaluate(&shuffled[start..start + size], shift, &[], false, false);
+		start += size;
+		return value;
+	}).sum();
+}
+
+/// Shift vectors, rotation matrices and shuffles of a single function, one of each for every component
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cec2017Data {
+	pub shifts: Vec<Vec<f64>>,
+	/// Row major matrices
+	pub rotations: Vec<Vec<f64>>,
+	/// Zero based permutations of the coordinates, empty for functions without hybrid parts
+	pub shuffles: Vec<Vec<usize>>,
+}
+
+impl Cec2017Data {
+	/// Random shift vectors inside the search range, random orthogonal rotations and random shuffles
+	pub fn generate(number: usize, dimensions: usize, seed: u64) -> Result<Self, SlimeError> {
+		let definition = check(number, dimensions)?;
+		let mut random_source = random::run_source(seed, number * 1000 + dimensions);
+		let components = definition.component_count();
+
+		let shifts = (0..components).map(|_| {
+			return (0..dimensions).map(|_| random_source.gen_range(-SHIFT_RANGE..SHIFT_RANGE)).collect();
+		}).collect();
+		let rotations = (0..components).map(|_| random_rotation(dimensions, &mut random_source)).collect();
+		let shuffles = if definition.is_hybrid() {
+			(0..components).map(|_| {
+				let mut shuffle = (0..dimensions).collect::<Vec<_>>();
+				shuffle.shuffle(&mut random_source);
+				return shuffle;
+			}).collect()
+		} else {
+			Vec::new()
+		};
+
+		return Ok(Self { shifts, rotations, shuffles });
+	}
+
+	/// Reads the files of the official distribution (`shift_data_N.txt`, `M_N_DD.txt` and `shuffle_data_N_DD.txt`)
+	/// from the directory
+	pub fn load(directory: &Path, number: usize, dimensions: usize) -> Result<Self, SlimeError> {
+		let definition = check(number, dimensions)?;
+		let components = definition.component_count();
+
+		let shift_text = read_data_file(&directory.join(format!("shift_data_{}.txt", number)))?;
+		let shifts = if components == 1 {
+			vec![parse_numbers(shift_text.split_whitespace().take(dimensions), dimensions)?]
+		} else {
+			// composition shifts are stored one per line, padded to the largest dimension count
+			shift_text.lines().filter(|line| !line.trim().is_empty()).take(components).map(|line| {
+				return parse_numbers(line.split_whitespace().take(dimensions), dimensions);
+			}).collect::<Result<Vec<_>, _>>()?
+		};
+
+		let rotation_text = read_data_file(&directory.join(format!("M_{}_D{}.txt", number, dimensions)))?;
+		let rotation_values = parse_numbers(rotation_text.split_whitespace(), components * dimensions * dimensions)?;
+		let rotations = rotation_values.chunks(dimensions * dimensions).map(<[f64]>::to_vec).collect();
+
+		let shuffles = if definition.is_hybrid() {
+			let shuffle_text = read_data_file(&directory.join(format!("shuffle_data_{}_D{}.txt", number, dimensions)))?;
+			let shuffle_values = parse_numbers(shuffle_text.split_whitespace(), components * dimensions)?;
+			shuffle_values.chunks(dimensions).map(|chunk| chunk.iter().map(|&index| index as usize - 1).collect()).collect()
+		} else {
+			Vec::new()
+		};
+
+		let result = Self { shifts, rotations, shuffles };
+		if result.shifts.len() != components {
+			return Err(SlimeError::InvalidBenchmarkData(format!("shift data of function {} has fewer than {} vectors", number, components)));
+		}
+		if result.shuffles.iter().any(|shuffle| !is_permutation(shuffle)) {
+			return Err(SlimeError::InvalidBenchmarkData(format!("shuffle data of function {} is not a permutation", number)));
+		}
+		return Ok(result);
+	}
+}
+
+fn read_data_file(path: &Path) -> Result<String, SlimeError> {
+	return std::fs::read_to_string(path).map_err(|error| SlimeError::InvalidBenchmarkData(format!("cannot read {}: {}", path.display(), error)));
+}
+
+fn parse_numbers<'a>(tokens: impl Iterator<Item = &'a str>, count: usize) -> Result<Vec<f64>, SlimeError> {
+	let values = tokens.take(count).map(|token| {
+		return token.parse::<f64>().map_err(|_| SlimeError::InvalidBenchmarkData(format!("`{}` is not a number", token)));
+	}).collect::<Result<Vec<_>, _>>()?;
+	if values.len() != count {
+		return Err(SlimeError::InvalidBenchmarkData(format!("expected {} values, found {}", count, values.len())));
+	}
+	return Ok(values);
+}
+
+fn is_permutation(shuffle: &[usize]) -> bool {
+	let mut seen = vec![false; shuffle.len()];
+	for &index in shuffle {
+		if index >= shuffle.len() || seen[index] {
+			return false;
+		}
+		seen[index] = true;
+	}
+	return true;
+}
+
+fn check(number: usize, dimensions: usize) -> Result<Definition, SlimeError> {
+	let definition = definition(number).ok_or_else(|| SlimeError::UnknownFunction(format!("cec2017-f{}", number)))?;
+	// hybrid functions need enough coordinates for all of their parts
+	if !DIMENSIONS.contains(&dimensions) || (definition.is_hybrid() && dimensions < 10) {
+		return Err(SlimeError::UnsupportedDimensions { problem: format!("cec2017-f{}", number), dimensions });
+	}
+	return Ok(definition);
+}
+
+/// Function of the CEC 2017 single objective bound constrained suite. Its global minimum is `100 * number`
+#[derive(Debug, Clone)]
+pub struct Cec2017 {
+	number: usize,
+	dimensions: usize,
+	definition: Definition,
+	data: Cec2017Data,
+	name: String,
+}
+
+impl Cec2017 {
+	/// Function with generated data. Results are comparable between runs of this crate, but not with
+	/// the literature, which uses the official data files
+	pub fn new(number: usize, dimensions: usize) -> Result<Self, SlimeError> {
+		return Self::with_data(number, dimensions, Cec2017Data::generate(number, dimensions, DEFAULT_SEED)?);
+	}
+
+	pub fn with_data(number: usize, dimensions: usize, data: Cec2017Data) -> Result<Self, SlimeError> {
+		let definition = check(number, dimensions)?;
+		let components = definition.component_count();
+		let valid = data.shifts.len() == components && data.shifts.iter().all(|shift| shift.len() == dimensions) &&
+			data.rotations.len() == components && data.rotations.iter().all(|rotation| rotation.len() == dimensions * dimensions) &&
+			(!definition.is_hybrid() || (data.shuffles.len() == components && data.shuffles.iter().all(|shuffle| shuffle.len() == dimensions && is_permutation(shuffle))));
+		if !valid {
+			return Err(SlimeError::InvalidBenchmarkData(format!("data does not match cec2017-f{} with {} dimensions", number, dimensions)));
+		}
+		return Ok(Self {
+			number, dimensions, definition, data,
+			name: format!("cec2017-f{}", number),
+		});
+	}
+
+	pub fn number(&self) -> usize {
+		return self.number;
+	}
+
+	pub fn calculate(&self, x: &[f64]) -> f64 {
+		let bias = 100.0 * self.number as f64;
+		match self.definition {
+			Definition::Simple(basic) => return basic.evaluate(x, &self.data.shifts[0], &self.data.rotations[0], true, true) + bias,
+			Definition::Hybrid(parts) => return hybrid(parts, x, &self.data.shifts[0], &self.data.rotations[0], &self.data.shuffles[0]) + bias,
+			Definition::Composition(parts) => {
+				let mut values = Vec::with_capacity(parts.len());
+				let mut weights = Vec::with_capacity(parts.len());
+				for (index, (part, lambda, delta)) in parts.iter().enumerate() {
+					let shift = &self.data.shifts[index];
+					let rotation = &self.data.rotations[index];
+					let value = match part {
+						Part::Basic(basic) => basic.evaluate(x, shift, rotation, true, true),
+						Part::Hybrid(number) => {
+							let Some(Definition::Hybrid(hybrid_parts)) = definition(*number) else {
+								unreachable!("composition part {} is not a hybrid function", number);
+							};
+							hybrid(hybrid_parts, x, shift, rotation, &self.data.shuffles[index])
+						}
+					};
+					values.push(lambda * value + 100.0 * index as f64);
+
+					let distance = x.iter().zip(shift).map(|(a, o)| (a - o).powi(2)).sum::<f64>();
+					weights.push(if distance != 0.0 {
+						(1.0 / distance).sqrt() * (-distance / 2.0 / x.len() as f64 / delta.powi(2)).exp()
+					} else {
+						INFINITE_WEIGHT
+					});
+				}
+				let weight_sum = weights.iter().sum::<f64>();
+				if weight_sum == 0.0 {
+					return values.iter().sum::<f64>() / values.len() as f64 + bias;
+				}
+				return values.iter().zip(&weights).map(|(value, weight)| weight / weight_sum * value).sum::<f64>() + bias;
+			}
+		}
+	}
+}
+
+impl<V: Vector> Objective<V> for Cec2017 {
+	fn value(&self, input: &V) -> f64 {
+		return self.calculate(input.as_slice());
+	}
+
+	fn bounds(&self, _dimensions: usize) -> Bounds<V> {
+		return Bounds::uniform(self.dimensions, -SEARCH_RANGE, SEARCH_RANGE);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some(&self.name);
+	}
+
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		let mut location = V::zeros(self.dimensions);
+		location.as_mut_slice().copy_from_slice(&self.data.shifts[0]);
+		return Some(Optimum {
+			value: 100.0 * self.number as f64,
+			location: Some(location),
+		});
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use rand::SeedableRng;
+
+	use crate::{cec2017::{Cec2017, Cec2017Data, FUNCTION_NUMBERS}, error::SlimeError, objective::Objective, random::RandomSource, vector::DynVector};
+
+	#[test]
+	fn optimum_test() {
+		for number in FUNCTION_NUMBERS {
+			for dimensions in [10, 30] {
+				let function = Cec2017::new(number, dimensions).unwrap();
+				let optimum = Objective::<DynVector>::optimum(&function, dimensions).unwrap();
+				let location = optimum.location.unwrap();
+				let value = function.value(&location);
+				assert!((value - optimum.value).abs() < 1e-6, "f{} ({}D) at its optimum is {}", number, dimensions, value);
+			}
+		}
+	}
+
+	#[test]
+	fn no_value_below_optimum_test() {
+		let mut random_source = RandomSource::seed_from_u64(0);
+		for number in FUNCTION_NUMBERS {
+			let function = Cec2017::new(number, 10).unwrap();
+			let bounds = Objective::<DynVector>::bounds(&function, 10);
+			for _ in 0..200 {
+				let value = function.value(&bounds.sample(&mut random_source));
+				assert!(value.is_finite() && value >= 100.0 * number as f64 - 1e-6, "f{} has value {}", number, value);
+			}
+		}
+	}
+
+	#[test]
+	fn unsupported_test() {
+		assert_eq!(Cec2017::new(2, 10).err(), Some(SlimeError::UnknownFunction("cec2017-f2".to_string())));
+		assert_eq!(Cec2017::new(11, 2).err(), Some(SlimeError::UnsupportedDimensions { problem: "cec2017-f11".to_string(), dimensions: 2 }));
+		assert!(Cec2017::new(5, 2).is_ok());
+		let data = Cec2017Data::generate(5, 10, 0).unwrap();
+		assert!(Cec2017::with_data(5, 20, data).is_err());
+	}
+}
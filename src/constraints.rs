@@ -0,0 +1,346 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::fitness::{self, Direction, FitnessSettings};
+
+/// Factor of the violation added to the objective value by the penalty methods, unless configured otherwise
+pub const DEFAULT_PENALTY_COEFFICIENT: f64 = 1e6;
+/// Largest deviation from zero with which an equality constraint counts as satisfied, the usual value of the CEC competitions
+pub const DEFAULT_EQUALITY_TOLERANCE: f64 = 1e-4;
+
+// the adaptive penalty changes after the best solution was feasible or infeasible for this many iterations in a row
+const ADAPTIVE_WINDOW: usize = 5;
+// different factors prevent the coefficient from cycling between two values
+const ADAPTIVE_DECREASE: f64 = 2.0;
+const ADAPTIVE_INCREASE: f64 = 3.0;
+// the adaptive coefficient stays within this ratio of the configured one
+const ADAPTIVE_RANGE: f64 = 1e3;
+// epsilon level schedule of Takahama and Sakai, starting at the violation of the top 20% of the initial population
+// and reaching zero after a fifth of the iterations
+const EPSILON_QUANTILE: f64 = 0.2;
+const EPSILON_EXPONENT: i32 = 5;
+const EPSILON_CONTROL: f64 = 0.2;
+
+/// How candidates violating the constraints are ranked against each other and against feasible ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConstraintHandling {
+	/// Adds the violation multiplied by a fixed coefficient to the objective value
+	StaticPenalty,
+	/// Penalty whose coefficient grows while the best solution stays infeasible and shrinks while it stays feasible
+	AdaptivePenalty,
+	/// Deb's rules: feasible beats infeasible, feasible ones are compared by value and infeasible ones by violation
+	#[default]
+	FeasibilityRules,
+	/// Feasibility rules treating violations below a level that decreases to zero during the run as feasible
+	EpsilonConstraint,
+}
+
+impl ConstraintHandling {
+	pub const ALL: [ConstraintHandling; 4] = [Self::StaticPenalty, Self::AdaptivePenalty, Self::FeasibilityRules, Self::EpsilonConstraint];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::StaticPenalty => return "static-penalty",
+			Self::AdaptivePenalty => return "adaptive-penalty",
+			Self::FeasibilityRules => return "feasibility-rules",
+			Self::EpsilonConstraint => return "epsilon",
+		}
+	}
+}
+
+impl FromStr for ConstraintHandling {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|handling| handling.name() == name).ok_or_else(|| {
+			return format!("unknown constraint handling `{}`, available strategies are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for ConstraintHandling {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", self.name());
+	}
+}
+
+/// Objective value of a point together with its total constraint violation, which is zero for feasible points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+	pub value: f64,
+	pub violation: f64,
+}
+
+impl Evaluation {
+	pub fn new(value: f64, violation: f64) -> Self {
+		return Self {
+			value, violation
+		};
+	}
+
+	pub fn is_feasible(&self) -> bool {
+		return self.violation == 0.0;
+	}
+}
+
+/// Sum of the amounts by which the constraints `g(x) <= 0` and `h(x) = 0` are violated.
+/// Equality constraints count as satisfied within the tolerance and NaN counts as an infinite violation
+pub fn violation(inequalities: &[f64], equalities: &[f64], equality_tolerance: f64) -> f64 {
+	let inequality_part = inequalities.iter().map(|g| if g.is_nan() { f64::INFINITY } else { g.max(0.0) });
+	let equality_part = equalities.iter().map(|h| if h.is_nan() { f64::INFINITY } else { (h.abs() - equality_tolerance).max(0.0) });
+	return inequality_part.chain(equality_part).sum();
+}
+
+/// Order of evaluations at one moment of a run, combining the direction with the constraint handling and its current parameters.
+/// All strategies reduce to `Direction::compare` when nothing is violated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ranking {
+	pub direction: Direction,
+	pub handling: ConstraintHandling,
+	pub penalty: f64,
+	pub epsilon: f64,
+}
+
+impl Ranking {
+	pub fn unconstrained(direction: Direction) -> Self {
+		return Self {
+			direction,
+			handling: ConstraintHandling::FeasibilityRules,
+			penalty: 0.0,
+			epsilon: 0.0,
+		};
+	}
+
+	fn penalized(self, evaluation: Evaluation) -> f64 {
+		// an infinite violation times a zero coefficient would be NaN
+		let penalty = if evaluation.violation == 0.0 { 0.0 } else { self.penalty * evaluation.violation };
+		return self.direction.signed(evaluation.value) + penalty;
+	}
+
+	fn effective_violation(self, violation: f64) -> f64 {
+		if violation <= self.epsilon {
+			return 0.0;
+		}
+		return violation;
+	}
+
+	/// Total order of evaluations, better ones first
+	pub fn compare(self, first: Evaluation, second: Evaluation) -> Ordering {
+		match self.handling {
+			ConstraintHandling::StaticPenalty | ConstraintHandling::AdaptivePenalty => {
+				return fitness::compare(self.penalized(first), self.penalized(second));
+			}
+			ConstraintHandling::FeasibilityRules | ConstraintHandling::EpsilonConstraint => {
+				let violations = fitness::compare(self.effective_violation(first.violation), self.effective_violation(second.violation));
+				return violations.then_with(|| self.direction.compare(first.value, second.value));
+			}
+		}
+	}
+
+	/// Whether the candidate is strictly better than the incumbent
+	pub fn is_better(self, candidate: Evaluation, incumbent: Evaluation) -> bool {
+		return self.compare(candidate, incumbent) == Ordering::Less;
+	}
+
+	/// Numbers ordered like `compare`, lower being better, for computations that need a magnitude rather than an order.
+	/// Feasibility based strategies place infeasible evaluations behind the worst feasible one of the group, shifted by their violation
+	pub fn keys(self, evaluations: &[Evaluation]) -> Vec<f64> {
+		match self.handling {
+			ConstraintHandling::StaticPenalty | ConstraintHandling::AdaptivePenalty => {
+				return evaluations.iter().map(|&evaluation| self.penalized(evaluation)).collect();
+			}
+			ConstraintHandling::FeasibilityRules | ConstraintHandling::EpsilonConstraint => {
+				let worst_feasible = evaluations.iter()
+					.filter(|evaluation| self.effective_violation(evaluation.violation) == 0.0)
+					.map(|evaluation| self.direction.signed(evaluation.value))
+					.max_by(|first, second| fitness::compare(*first, *second))
+					.unwrap_or(0.0);
+				return evaluations.iter().map(|evaluation| {
+					let violation = self.effective_violation(evaluation.violation);
+					if violation == 0.0 {
+						return self.direction.signed(evaluation.value);
+					}
+					return worst_feasible + violation;
+				}).collect();
+			}
+		}
+	}
+}
+
+/// Parameters of the constraint handling that change during a run
+#[derive(Debug, Clone)]
+pub struct ConstraintState {
+	direction: Direction,
+	handling: ConstraintHandling,
+	initial_penalty: f64,
+	penalty: f64,
+	initial_epsilon: f64,
+	epsilon: f64,
+	feasible_streak: usize,
+	infeasible_streak: usize,
+}
+
+impl ConstraintState {
+	pub fn new(settings: &FitnessSettings) -> Self {
+		return Self {
+			direction: settings.direction,
+			handling: settings.constraint_handling,
+			initial_penalty: settings.penalty_coefficient,
+			penalty: settings.penalty_coefficient,
+			initial_epsilon: 0.0,
+			epsilon: 0.0,
+			feasible_streak: 0,
+			infeasible_streak: 0,
+		};
+	}
+
+	/// Restores the initial parameters, deriving the initial epsilon level from the violations of the initial population
+	pub fn start(&mut self, violations: impl Iterator<Item = f64>) {
+		let mut violations = violations.collect::<Vec<_>>();
+		violations.sort_unstable_by(|first, second| fitness::compare(*first, *second));
+		self.initial_epsilon = violations.get((violations.len() as f64 * EPSILON_QUANTILE) as usize).copied().filter(|epsilon| epsilon.is_finite()).unwrap_or(0.0);
+		self.epsilon = if self.handling == ConstraintHandling::EpsilonConstraint { self.initial_epsilon } else { 0.0 };
+		self.penalty = self.initial_penalty;
+		self.feasible_streak = 0;
+		self.infeasible_streak = 0;
+	}
+
+	/// Adapts the parameters after an iteration, given the share of the run already performed
+	pub fn update(&mut self, progress: f64, best_is_feasible: bool) {
+		match self.handling {
+			ConstraintHandling::AdaptivePenalty => {
+				if best_is_feasible {
+					self.feasible_streak += 1;
+					self.infeasible_streak = 0;
+				} else {
+					self.infeasible_streak += 1;
+					self.feasible_streak = 0;
+				}
+				if self.feasible_streak >= ADAPTIVE_WINDOW {
+					self.penalty /= ADAPTIVE_DECREASE;
+					self.feasible_streak = 0;
+				} else if self.infeasible_streak >= ADAPTIVE_WINDOW {
+					self.penalty *= ADAPTIVE_INCREASE;
+					self.infeasible_streak = 0;
+				}
+				self.penalty = self.penalty.clamp(self.initial_penalty / ADAPTIVE_RANGE, self.initial_penalty * ADAPTIVE_RANGE);
+			}
+			ConstraintHandling::EpsilonConstraint => {
+				self.epsilon = if progress < EPSILON_CONTROL {
+					self.initial_epsilon * (1.0 - progress / EPSILON_CONTROL).powi(EPSILON_EXPONENT)
+				} else {
+					0.0
+				};
+			}
+			ConstraintHandling::StaticPenalty | ConstraintHandling::FeasibilityRules => {}
+		}
+	}
+
+	pub fn ranking(&self) -> Ranking {
+		return Ranking {
+			direction: self.direction,
+			handling: self.handling,
+			penalty: self.penalty,
+			epsilon: self.epsilon,
+		};
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::{cmp::Ordering, sync::Arc};
+
+	use rand::SeedableRng;
+
+	use crate::{bounds::Bounds, constraints::{violation, ConstraintHandling, ConstraintState, Evaluation, Ranking}, fitness::{Direction, FitnessSettings}, multi_swarm::MultiSwarm, objective::{FnObjective, Objective}, optimizer::Optimizer, particles, random::RandomSource, slime, vector::VectorN};
+
+	fn ranking(handling: ConstraintHandling) -> Ranking {
+		return Ranking { handling, penalty: 10.0, ..Ranking::unconstrained(Direction::Minimize) };
+	}
+
+	#[test]
+	fn violation_test() {
+		assert_eq!(violation(&[-1.0, 2.0, 0.5], &[], 1e-4), 2.5);
+		assert_eq!(violation(&[], &[0.00005, -1.0], 1e-4), 1.0 - 1e-4);
+		assert_eq!(violation(&[f64::NAN], &[], 1e-4), f64::INFINITY);
+		assert_eq!(violation(&[], &[], 1e-4), 0.0);
+	}
+
+	#[test]
+	fn ranking_test() {
+		let feasible_bad = Evaluation::new(5.0, 0.0);
+		let feasible_good = Evaluation::new(1.0, 0.0);
+		let slightly_infeasible = Evaluation::new(0.5, 0.1);
+		let very_infeasible = Evaluation::new(-100.0, 3.0);
+
+		let rules = ranking(ConstraintHandling::FeasibilityRules);
+		assert!(rules.is_better(feasible_good, feasible_bad));
+		assert!(rules.is_better(feasible_bad, slightly_infeasible));
+		assert!(rules.is_better(slightly_infeasible, very_infeasible));
+
+		// 0.5 + 10 * 0.1 lies between both feasible values, -100 + 10 * 3 beats everything
+		let penalty = ranking(ConstraintHandling::StaticPenalty);
+		assert!(penalty.is_better(slightly_infeasible, feasible_bad));
+		assert!(penalty.is_better(feasible_good, slightly_infeasible));
+		assert!(penalty.is_better(very_infeasible, feasible_good));
+
+		let epsilon = Ranking { epsilon: 0.5, ..ranking(ConstraintHandling::EpsilonConstraint) };
+		assert!(epsilon.is_better(slightly_infeasible, feasible_good));
+		assert!(epsilon.is_better(feasible_good, very_infeasible));
+
+		for ranking in [rules, penalty, epsilon] {
+			let evaluations = [feasible_bad, feasible_good, slightly_infeasible, very_infeasible];
+			let keys = ranking.keys(&evaluations);
+			for first in 0..evaluations.len() {
+				for second in 0..evaluations.len() {
+					let order = ranking.compare(evaluations[first], evaluations[second]);
+					assert!(order == Ordering::Equal || keys[first].partial_cmp(&keys[second]) == Some(order), "{:?} disagrees with its keys", ranking.handling);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn state_schedule_test() {
+		let settings = FitnessSettings::new().with_constraint_handling(ConstraintHandling::EpsilonConstraint);
+		let mut state = ConstraintState::new(&settings);
+		state.start([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0].into_iter());
+		assert_eq!(state.ranking().epsilon, 2.0);
+		state.update(0.1, false);
+		assert_eq!(state.ranking().epsilon, 2.0 / 32.0);
+		state.update(0.5, false);
+		assert_eq!(state.ranking().epsilon, 0.0);
+
+		let settings = FitnessSettings::new().with_constraint_handling(ConstraintHandling::AdaptivePenalty).with_penalty_coefficient(100.0);
+		let mut state = ConstraintState::new(&settings);
+		state.start(std::iter::empty());
+		for _ in 0..5 {
+			state.update(0.0, false);
+		}
+		assert_eq!(state.ranking().penalty, 300.0);
+		for _ in 0..10 {
+			state.update(0.0, true);
+		}
+		assert_eq!(state.ranking().penalty, 75.0);
+	}
+
+	#[test]
+	fn constrained_sphere_test() {
+		// the constraint x + y >= 1 moves the minimum of the sphere from the origin to (0.5, 0.5)
+		let objective: Arc<dyn Objective<VectorN<2>>> = Arc::new(FnObjective::new(|input: &VectorN<2>| input.coordinates.iter().map(|a| a.powi(2)).sum(), Bounds::uniform(2, -5.0, 5.0))
+			.with_inequality_constraints(|input: &VectorN<2>| vec![1.0 - input.coordinates[0] - input.coordinates[1]]));
+		for handling in ConstraintHandling::ALL {
+			let settings = FitnessSettings::new().with_constraint_handling(handling);
+			let mut slime_world = MultiSwarm::<VectorN<2>, _>::new(3, 0.1, RandomSource::seed_from_u64(0), |rng_source| {
+				return slime::WorldState::new(20, objective.clone(), 2, 200, 0.03, settings, rng_source);
+			}).unwrap();
+			let coefficients = particles::Coefficients { social: 0.6, cognitive: 0.5, inertia: 0.7 };
+			let mut particle_world = particles::WorldState::new(20, objective.clone(), 2, 200, coefficients, settings, RandomSource::seed_from_u64(0)).unwrap();
+			for result in [slime_world.run().unwrap(), particle_world.run().unwrap()] {
+				assert_eq!(result.best_violation, 0.0, "{} ended infeasible", handling);
+				assert!((result.best_value - 0.5).abs() < 0.1, "{} found {}", handling, result.best_value);
+			}
+		}
+	}
+}
@@ -0,0 +1,256 @@
+use std::f64::consts::{PI, SQRT_2};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{bounds::Bounds, error::SlimeError, objective::{Objective, Optimum}, vector::Vector};
+
+/// Constrained engineering design problems used in the SMA paper and most of the metaheuristics literature.
+/// All are minimized and have a fixed dimension count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineeringProblem {
+	/// Cost of a cylindrical vessel: shell thickness, head thickness, inner radius and length
+	PressureVessel,
+	/// Cost of a beam welded to a wall: weld thickness, weld length, bar height and bar thickness
+	WeldedBeam,
+	/// Weight of a tension/compression spring: wire diameter, coil diameter and number of active coils
+	Spring,
+	/// Weight of a gearbox: face width, teeth module, pinion teeth, two shaft lengths and two shaft diameters
+	SpeedReducer,
+	/// Volume of a truss of three bars: cross sections of the outer and the middle bar
+	ThreeBarTruss,
+}
+
+impl EngineeringProblem {
+	pub const ALL: [EngineeringProblem; 5] = [Self::PressureVessel, Self::WeldedBeam, Self::Spring, Self::SpeedReducer, Self::ThreeBarTruss];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::PressureVessel => return "pressure-vessel",
+			Self::WeldedBeam => return "welded-beam",
+			Self::Spring => return "spring",
+			Self::SpeedReducer => return "speed-reducer",
+			Self::ThreeBarTruss => return "three-bar-truss",
+		}
+	}
+
+	pub fn dimensions(self) -> usize {
+		return self.lower_bounds().len();
+	}
+
+	fn lower_bounds(self) -> &'static [f64] {
+		match self {
+			Self::PressureVessel => return &[0.0, 0.0, 10.0, 10.0],
+			Self::WeldedBeam => return &[0.1, 0.1, 0.1, 0.1],
+			Self::Spring => return &[0.05, 0.25, 2.0],
+			Self::SpeedReducer => return &[2.6, 0.7, 17.0, 7.3, 7.3, 2.9, 5.0],
+			Self::ThreeBarTruss => return &[0.0, 0.0],
+		}
+	}
+
+	fn upper_bounds(self) -> &'static [f64] {
+		match self {
+			Self::PressureVessel => return &[99.0, 99.0, 200.0, 200.0],
+			Self::WeldedBeam => return &[2.0, 10.0, 10.0, 2.0],
+			Self::Spring => return &[2.0, 1.3, 15.0],
+			Self::SpeedReducer => return &[3.6, 0.8, 28.0, 8.3, 8.3, 3.9, 5.5],
+			Self::ThreeBarTruss => return &[1.0, 1.0],
+		}
+	}
+
+	/// Best solution reported in the literature for the continuous formulation
+	pub fn best_known(self) -> (f64, &'static [f64]) {
+		match self {
+			Self::PressureVessel => return (5885.332773, &[0.778168641, 0.384649163, 40.31961872, 200.0]),
+			Self::WeldedBeam => return (1.724852309, &[0.20572964, 3.47048867, 9.03662391, 0.20572964]),
+			Self::Spring => return (0.012665233, &[0.051689061, 0.356717736, 11.28896595]),
+			Self::SpeedReducer => return (2994.471066, &[3.5, 0.7, 17.0, 7.3, 7.715319911, 3.350214666, 5.286654465]),
+			Self::ThreeBarTruss => return (263.8958434, &[0.788675134, 0.408248290]),
+		}
+	}
+
+	// the published constants are kept, 0.7854 of the speed reducer is only close to pi / 4
+	#[allow(clippy::approx_constant)]
+	pub fn calculate(self, x: &[f64]) -> f64 {
+		match self {
+			Self::PressureVessel => {
+				return 0.6224 * x[0] * x[2] * x[3] + 1.7781 * x[1] * x[2].powi(2) + 3.1661 * x[0].powi(2) * x[3] + 19.84 * x[0].powi(2) * x[2];
+			}
+			Self::WeldedBeam => return 1.10471 * x[0].powi(2) * x[1] + 0.04811 * x[2] * x[3] * (14.0 + x[1]),
+			Self::Spring => return (x[2] + 2.0) * x[1] * x[0].powi(2),
+			Self::SpeedReducer => {
+				return 0.7854 * x[0] * x[1].powi(2) * (3.3333 * x[2].powi(2) + 14.9334 * x[2] - 43.0934)
+					- 1.508 * x[0] * (x[5].powi(2) + x[6].powi(2))
+					+ 7.4777 * (x[5].powi(3) + x[6].powi(3))
+					+ 0.7854 * (x[3] * x[5].powi(2) + x[4] * x[6].powi(2));
+			}
+			Self::ThreeBarTruss => return (2.0 * SQRT_2 * x[0] + x[1]) * 100.0,
+		}
+	}
+
+	/// Values of the constraints `g(x) <= 0`
+	pub fn constraints(self, x: &[f64]) -> Vec<f64> {
+		match self {
+			Self::PressureVessel => return vec![
+				-x[0] + 0.0193 * x[2],
+				-x[1] + 0.00954 * x[2],
+				-PI * x[2].powi(2) * x[3] - 4.0 / 3.0 * PI * x[2].powi(3) + 1_296_000.0,
+				x[3] - 240.0,
+			],
+			Self::WeldedBeam => {
+				let (load, length, elasticity, shear_modulus) = (6000.0, 14.0, 30e6, 12e6);
+				let (max_shear, max_stress, max_deflection) = (13600.0, 30000.0, 0.25);
+
+				let primary_shear = load / (SQRT_2 * x[0] * x[1]);
+				let moment = load * (length + x[1] / 2.0);
+				let radius = (x[1].powi(2) / 4.0 + ((x[0] + x[2]) / 2.0).powi(2)).sqrt();
+				let polar_moment = 2.0 * (SQRT_2 * x[0] * x[1] * (x[1].powi(2) / 12.0 + ((x[0] + x[2]) / 2.0).powi(2)));
+				let secondary_shear = moment * radius / polar_moment;
+				let shear = (primary_shear.powi(2) + 2.0 * primary_shear * secondary_shear * x[1] / (2.0 * radius) + secondary_shear.powi(2)).sqrt();
+				let stress = 6.0 * load * length / (x[3] * x[2].powi(2));
+				let deflection = 4.0 * load * length.powi(3) / (elasticity * x[2].powi(3) * x[3]);
+				let buckling_load = 4.013 * elasticity * (x[2].powi(2) * x[3].powi(6) / 36.0).sqrt() / length.powi(2)
+					* (1.0 - x[2] / (2.0 * length) * (elasticity / (4.0 * shear_modulus)).sqrt());
+
+				return vec![
+					shear - max_shear,
+					stress - max_stress,
+					x[0] - x[3],
+					0.10471 * x[0].powi(2) + 0.04811 * x[2] * x[3] * (14.0 + x[1]) - 5.0,
+					0.125 - x[0],
+					deflection - max_deflection,
+					load - buckling_load,
+				];
+			}
+			Self::Spring => return vec![
+				1.0 - x[1].powi(3) * x[2] / (71785.0 * x[0].powi(4)),
+				(4.0 * x[1].powi(2) - x[0] * x[1]) / (12566.0 * (x[1] * x[0].powi(3) - x[0].powi(4))) + 1.0 / (5108.0 * x[0].powi(2)) - 1.0,
+				1.0 - 140.45 * x[0] / (x[1].powi(2) * x[2]),
+				(x[0] + x[1]) / 1.5 - 1.0,
+			],
+			Self::SpeedReducer => return vec![
+				27.0 / (x[0] * x[1].powi(2) * x[2]) - 1.0,
+				397.5 / (x[0] * x[1].powi(2) * x[2].powi(2)) - 1.0,
+				1.93 * x[3].powi(3) / (x[1] * x[2] * x[5].powi(4)) - 1.0,
+				1.93 * x[4].powi(3) / (x[1] * x[2] * x[6].powi(4)) - 1.0,
+				((745.0 * x[3] / (x[1] * x[2])).powi(2) + 16.9e6).sqrt() / (110.0 * x[5].powi(3)) - 1.0,
+				((745.0 * x[4] / (x[1] * x[2])).powi(2) + 157.5e6).sqrt() / (85.0 * x[6].powi(3)) - 1.0,
+				x[1] * x[2] / 40.0 - 1.0,
+				5.0 * x[1] / x[0] - 1.0,
+				x[0] / (12.0 * x[1]) - 1.0,
+				(1.5 * x[5] + 1.9) / x[3] - 1.0,
+				(1.1 * x[6] + 1.9) / x[4] - 1.0,
+			],
+			Self::ThreeBarTruss => {
+				let (load, max_stress) = (2.0, 2.0);
+				let denominator = SQRT_2 * x[0].powi(2) + 2.0 * x[0] * x[1];
+				return vec![
+					(SQRT_2 * x[0] + x[1]) / denominator * load - max_stress,
+					x[1] / denominator * load - max_stress,
+					1.0 / (SQRT_2 * x[1] + x[0]) * load - max_stress,
+				];
+			}
+		}
+	}
+}
+
+impl FromStr for EngineeringProblem {
+	type Err = SlimeError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|&problem| problem.name() == name).ok_or_else(|| SlimeError::UnknownFunction(name.to_string()));
+	}
+}
+
+impl Display for EngineeringProblem {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", EngineeringProblem::name(*self));
+	}
+}
+
+impl<V: Vector> Objective<V> for EngineeringProblem {
+	fn value(&self, input: &V) -> f64 {
+		return self.calculate(input.as_slice());
+	}
+
+	fn bounds(&self, _dimensions: usize) -> Bounds<V> {
+		let mut result = Bounds::new(V::zeros(self.dimensions()), V::zeros(self.dimensions()));
+		result.lower.as_mut_slice().copy_from_slice(self.lower_bounds());
+		result.upper.as_mut_slice().copy_from_slice(self.upper_bounds());
+		return result;
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some(EngineeringProblem::name(*self));
+	}
+
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		let (value, solution) = self.best_known();
+		let mut location = V::zeros(self.dimensions());
+		location.as_mut_slice().copy_from_slice(solution);
+		return Some(Optimum {
+			value,
+			location: Some(location),
+		});
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.constraints(input.as_slice());
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{constraints, engineering::EngineeringProblem, fitness::FitnessSettings, optimizer::Optimizer, particles, random::RandomSource, slime, vector::DynVector};
+
+	#[test]
+	fn best_known_test() {
+		for problem in EngineeringProblem::ALL {
+			let (value, solution) = problem.best_known();
+			assert!((problem.calculate(solution) - value).abs() / value < 1e-6, "{} at its best known solution is {}", problem, problem.calculate(solution));
+			// published solutions are rounded, so active constraints can be violated by a tiny amount
+			let violation = constraints::violation(&problem.constraints(solution), &[], 0.0);
+			assert!(violation < 1e-3, "{} is violated by {}", problem, violation);
+			assert_eq!(problem.dimensions(), solution.len());
+			assert_eq!(problem.lower_bounds().len(), problem.upper_bounds().len());
+		}
+	}
+
+	#[test]
+	fn feasible_designs_test() {
+		// published tables report the best of several runs, both algorithms get the same budget
+		for (problem, factor) in [
+			(EngineeringProblem::PressureVessel, 1.02),
+			(EngineeringProblem::WeldedBeam, 1.02),
+			(EngineeringProblem::Spring, 1.01),
+			(EngineeringProblem::SpeedReducer, 1.03),
+			(EngineeringProblem::ThreeBarTruss, 1.01),
+		] {
+			let dimensions = problem.dimensions();
+			let (best_known, _) = problem.best_known();
+			let coefficients = particles::Coefficients { social: 1.5, cognitive: 1.5, inertia: 0.7 };
+			let make_worlds = |seed| -> [Box<dyn Optimizer<DynVector>>; 2] {
+				return [
+					Box::new(slime::WorldState::new(30, Arc::new(problem), dimensions, 1000, 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(seed)).unwrap()),
+					Box::new(particles::WorldState::new(30, Arc::new(problem), dimensions, 1000, coefficients, FitnessSettings::new(), RandomSource::seed_from_u64(seed)).unwrap()),
+				];
+			};
+			let mut best = [f64::INFINITY; 2];
+			for seed in 0..5 {
+				for (world, best) in make_worlds(seed).iter_mut().zip(&mut best) {
+					let result = world.run().unwrap();
+					assert_eq!(result.best_violation, 0.0, "{} ended infeasible", problem);
+					// nothing feasible is better than the best known solution, up to the rounding of its coordinates
+					assert!(result.best_value > best_known * (1.0 - 1e-6), "{} found {}", problem, result.best_value);
+					*best = best.min(result.best_value);
+				}
+			}
+			for (algorithm, best) in ["slime mold", "particle swarm"].iter().zip(best) {
+				assert!(best < best_known * factor, "{} got {} on {}", algorithm, best, problem);
+			}
+		}
+	}
+}
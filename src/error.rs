@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use crate::{bounds::Bounds, engineering::EngineeringProblem, functions::Functions, vector::Vector};
+
+/// Errors reported while configuring or running the optimizers
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlimeError {
+	InvalidPopulationSize(usize),
+	InvalidSwarmCount(usize),
+	/// The z-parameter is a probability, so it has to lie between 0 and 1
+	InvalidZParameter(f64),
+	InvalidIterationCount(usize),
+	InvalidMigrationThreshold(f64),
+	/// The external archive of a multi-objective optimizer has to hold at least one solution
+	InvalidArchiveSize(usize),
+	/// Bounds of the objective are empty, inverted or have a different dimension count than requested
+	InvalidBounds { dimensions: usize },
+	/// The objective returned NaN or an infinite value and the policy does not allow recovering from it
+	NonFiniteFitness,
+	UnknownFunction(String),
+	/// The benchmark is not defined for the dimension count
+	UnsupportedDimensions { problem: String, dimensions: usize },
+	/// Benchmark data files are missing or malformed
+	InvalidBenchmarkData(String),
+	/// Scale factors or rotation matrix of a transformed objective are unusable
+	InvalidTransform(String),
+}
+
+impl Display for SlimeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::InvalidPopulationSize(size) => return write!(f, "population size must be at least 1, got {}", size),
+			Self::InvalidSwarmCount(count) => return write!(f, "swarm count must be at least 1, got {}", count),
+			Self::InvalidZParameter(z) => return write!(f, "z-parameter must lie between 0 and 1, got {}", z),
+			Self::InvalidIterationCount(count) => return write!(f, "iteration count must be at least 1, got {}", count),
+			Self::InvalidMigrationThreshold(threshold) => return write!(f, "migration threshold must be a non-negative number, got {}", threshold),
+			Self::InvalidArchiveSize(size) => return write!(f, "archive size must be at least 1, got {}", size),
+			Self::InvalidBounds { dimensions } => return write!(f, "bounds of the objective are empty, inverted or do not have {} dimensions", dimensions),
+			Self::NonFiniteFitness => return write!(f, "objective returned NaN or an infinite value"),
+			Self::UnknownFunction(name) => {
+				let available = Functions::ALL.map(Functions::name).into_iter().chain(EngineeringProblem::ALL.map(EngineeringProblem::name)).collect::<Vec<_>>().join(", ");
+				return write!(f, "unknown function `{}`, available functions are: {}", name, available);
+			}
+			Self::UnsupportedDimensions { problem, dimensions } => return write!(f, "{} is not defined for {} dimensions", problem, dimensions),
+			Self::InvalidBenchmarkData(message) => return write!(f, "invalid benchmark data: {}", message),
+			Self::InvalidTransform(message) => return write!(f, "invalid transform: {}", message),
+		}
+	}
+}
+
+impl std::error::Error for SlimeError {}
+
+/// Checks the settings shared by every population based optimizer
+pub(crate) fn validate_population<V: Vector>(population_size: usize, iteration_count: usize, bounds: &Bounds<V>, dimensions: usize) -> Result<(), SlimeError> {
+	if population_size == 0 {
+		return Err(SlimeError::InvalidPopulationSize(population_size));
+	}
+	if iteration_count == 0 {
+		return Err(SlimeError::InvalidIterationCount(iteration_count));
+	}
+	if !bounds.is_valid() || bounds.dimensions() != dimensions {
+		return Err(SlimeError::InvalidBounds { dimensions });
+	}
+	return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{bounds::Bounds, error::{validate_population, SlimeError}, fitness::FitnessSettings, functions::Functions, multi_swarm::MultiSwarm, optimizer::Optimizer, random::RandomSource, slime, vector::{DynVector, VectorN}};
+
+	#[test]
+	fn validation_test() {
+		let bounds = Bounds::<DynVector>::uniform(3, -1.0, 1.0);
+		assert_eq!(validate_population(10, 100, &bounds, 3), Ok(()));
+		assert_eq!(validate_population(0, 100, &bounds, 3), Err(SlimeError::InvalidPopulationSize(0)));
+		assert_eq!(validate_population(10, 0, &bounds, 3), Err(SlimeError::InvalidIterationCount(0)));
+		assert_eq!(validate_population(10, 100, &bounds, 4), Err(SlimeError::InvalidBounds { dimensions: 4 }));
+		assert_eq!(validate_population(10, 100, &Bounds::<DynVector>::uniform(3, 1.0, -1.0), 3), Err(SlimeError::InvalidBounds { dimensions: 3 }));
+		assert_eq!(validate_population(10, 100, &Bounds::<DynVector>::uniform(0, -1.0, 1.0), 0), Err(SlimeError::InvalidBounds { dimensions: 0 }));
+	}
+
+	#[test]
+	fn constructor_errors_test() {
+		let make_slime = |pop_size, iteration_count, z_parameter| {
+			return slime::WorldState::<VectorN<3>>::new(pop_size, Arc::new(Functions::Ackley), 3, iteration_count, z_parameter, FitnessSettings::new(), RandomSource::seed_from_u64(0));
+		};
+		assert_eq!(make_slime(10, 100, 1.5).err(), Some(SlimeError::InvalidZParameter(1.5)));
+		assert_eq!(make_slime(0, 100, 0.03).err(), Some(SlimeError::InvalidPopulationSize(0)));
+		assert!(make_slime(1, 1, 0.03).unwrap().run().is_ok());
+
+		let no_swarms = MultiSwarm::<VectorN<3>, _>::new(0, 0.1, RandomSource::seed_from_u64(0), |rng_source| {
+			return slime::WorldState::new(10, Arc::new(Functions::Ackley), 3, 100, 0.03, FitnessSettings::new(), rng_source);
+		});
+		assert_eq!(no_swarms.err(), Some(SlimeError::InvalidSwarmCount(0)));
+	}
+}
@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::constraints::{ConstraintHandling, DEFAULT_EQUALITY_TOLERANCE, DEFAULT_PENALTY_COEFFICIENT};
+
+/// Number of random positions tried by `NonFinitePolicy::Resample` before giving up
+pub const MAX_RESAMPLES: usize = 100;
+
+/// Handling of NaN and infinite values returned by the objective
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFinitePolicy {
+	/// Replace the value with the worst finite value, ranking the candidate behind every finite one
+	#[default]
+	Worst,
+	/// Move the candidate to random positions inside the bounds until the value is finite
+	Resample,
+	/// Stop the optimizer with `SlimeError::NonFiniteFitness`
+	Abort,
+}
+
+impl NonFinitePolicy {
+	pub const ALL: [NonFinitePolicy; 3] = [Self::Worst, Self::Resample, Self::Abort];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Worst => return "worst",
+			Self::Resample => return "resample",
+			Self::Abort => return "abort",
+		}
+	}
+}
+
+impl FromStr for NonFinitePolicy {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|policy| policy.name() == name).ok_or_else(|| {
+			return format!("unknown policy `{}`, available policies are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for NonFinitePolicy {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", self.name());
+	}
+}
+
+/// Whether the optimizers look for the lowest or the highest objective value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+	#[default]
+	Minimize,
+	Maximize,
+}
+
+impl Direction {
+	pub const ALL: [Direction; 2] = [Self::Minimize, Self::Maximize];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Minimize => return "minimize",
+			Self::Maximize => return "maximize",
+		}
+	}
+
+	/// Value of the equivalent minimization problem, maximizing `f` is the same as minimizing `-f`
+	pub fn signed(self, value: f64) -> f64 {
+		match self {
+			Self::Minimize => return value,
+			Self::Maximize => return -value,
+		}
+	}
+
+	/// Total order of fitness values, better values first and NaN after everything else
+	pub fn compare(self, first: f64, second: f64) -> Ordering {
+		return compare(self.signed(first), self.signed(second));
+	}
+
+	/// Whether the candidate value is strictly better than the incumbent
+	pub fn is_better(self, candidate: f64, incumbent: f64) -> bool {
+		return self.compare(candidate, incumbent) == Ordering::Less;
+	}
+
+	/// Value worse than any other, used before anything was evaluated
+	pub fn worst(self) -> f64 {
+		return self.signed(f64::INFINITY);
+	}
+
+	/// Finite value worse than any other, replacing non-finite values with `NonFinitePolicy::Worst`
+	pub fn worst_finite(self) -> f64 {
+		return self.signed(f64::MAX);
+	}
+}
+
+impl FromStr for Direction {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|direction| direction.name() == name).ok_or_else(|| {
+			return format!("unknown direction `{}`, available directions are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for Direction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", self.name());
+	}
+}
+
+/// How the optimizers interpret the values returned by the objective
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FitnessSettings {
+	pub direction: Direction,
+	pub non_finite_policy: NonFinitePolicy,
+	/// Additional evaluations averaged into the value of the best solution every iteration and into the value
+	/// of every candidate that would replace it, so a lucky evaluation of a noisy objective does not stick
+	pub reevaluations: usize,
+	pub constraint_handling: ConstraintHandling,
+	/// Initial coefficient of the penalty methods
+	pub penalty_coefficient: f64,
+	pub equality_tolerance: f64,
+}
+
+impl Default for FitnessSettings {
+	fn default() -> Self {
+		return Self {
+			direction: Direction::default(),
+			non_finite_policy: NonFinitePolicy::default(),
+			reevaluations: 0,
+			constraint_handling: ConstraintHandling::default(),
+			penalty_coefficient: DEFAULT_PENALTY_COEFFICIENT,
+			equality_tolerance: DEFAULT_EQUALITY_TOLERANCE,
+		};
+	}
+}
+
+impl FitnessSettings {
+	pub fn new() -> Self {
+		return Self::default();
+	}
+
+	pub fn with_direction(mut self, direction: Direction) -> Self {
+		self.direction = direction;
+		return self;
+	}
+
+	pub fn with_non_finite_policy(mut self, non_finite_policy: NonFinitePolicy) -> Self {
+		self.non_finite_policy = non_finite_policy;
+		return self;
+	}
+
+	pub fn with_reevaluations(mut self, reevaluations: usize) -> Self {
+		self.reevaluations = reevaluations;
+		return self;
+	}
+
+	pub fn with_constraint_handling(mut self, constraint_handling: ConstraintHandling) -> Self {
+		self.constraint_handling = constraint_handling;
+		return self;
+	}
+
+	pub fn with_penalty_coefficient(mut self, penalty_coefficient: f64) -> Self {
+		self.penalty_coefficient = penalty_coefficient;
+		return self;
+	}
+
+	pub fn with_equality_tolerance(mut self, equality_tolerance: f64) -> Self {
+		self.equality_tolerance = equality_tolerance;
+		return self;
+	}
+}
+
+/// Ascending order of fitness values with NaN after everything else
+pub fn compare(first: f64, second: f64) -> Ordering {
+	return first.partial_cmp(&second).unwrap_or_else(|| first.is_nan().cmp(&second.is_nan()));
+}
+
+#[cfg(test)]
+mod test {
+	use std::{cmp::Ordering, sync::Arc};
+
+	use rand::SeedableRng;
+
+	use crate::{bounds::Bounds, error::SlimeError, fitness::{compare, Direction, FitnessSettings, NonFinitePolicy}, multi_swarm::MultiSwarm, functions::Functions, objective::{FnObjective, Objective}, optimizer::Optimizer, particles, random::RandomSource, slime, vector::{Vector, VectorN}};
+
+	// NaN on half of the domain and infinite on a quarter of it
+	fn pathological(input: &VectorN<2>) -> f64 {
+		if input.coordinates[0] > 0.0 {
+			return f64::NAN;
+		}
+		if input.coordinates[1] > 0.5 {
+			return f64::INFINITY;
+		}
+		return input.coordinates.iter().map(|a| a.powi(2)).sum();
+	}
+
+	fn objective() -> Arc<dyn Objective<VectorN<2>>> {
+		return Arc::new(FnObjective::new(pathological, Bounds::uniform(2, -1.0, 1.0)));
+	}
+
+	#[test]
+	fn compare_test() {
+		assert_eq!(compare(1.0, 2.0), Ordering::Less);
+		assert_eq!(compare(f64::NAN, f64::INFINITY), Ordering::Greater);
+		assert_eq!(compare(f64::NEG_INFINITY, f64::NAN), Ordering::Less);
+		assert_eq!(compare(f64::NAN, f64::NAN), Ordering::Equal);
+		assert!(!Direction::Minimize.is_better(f64::NAN, f64::MAX));
+		assert!(!Direction::Maximize.is_better(f64::NAN, f64::MIN));
+		assert!(Direction::Maximize.is_better(2.0, 1.0));
+		assert_eq!(Direction::Maximize.compare(f64::NAN, f64::NEG_INFINITY), Ordering::Greater);
+		assert_eq!("resample".parse(), Ok(NonFinitePolicy::Resample));
+		assert_eq!("maximize".parse(), Ok(Direction::Maximize));
+	}
+
+	#[test]
+	fn worst_policy_test() {
+		let mut world = MultiSwarm::<VectorN<2>, _>::new(3, 0.0, RandomSource::seed_from_u64(0), |rng_source| {
+			return slime::WorldState::new(10, objective(), 2, 50, 0.03, FitnessSettings::new(), rng_source);
+		}).unwrap();
+		let result = world.run().unwrap();
+
+		assert!(result.best_value.is_finite());
+		assert!(pathological(&result.best_solution).is_finite());
+		assert!(world.candidates().iter().all(|candidate| candidate.fitness() <= f64::MAX));
+	}
+
+	#[test]
+	fn resample_policy_test() {
+		let coefficients = particles::Coefficients { social: 0.6, cognitive: 0.5, inertia: 0.7 };
+		let mut world = particles::WorldState::new(10, objective(), 2, 50, coefficients, FitnessSettings::new().with_non_finite_policy(NonFinitePolicy::Resample), RandomSource::seed_from_u64(0)).unwrap();
+		world.run().unwrap();
+
+		assert!(world.best_value().is_finite());
+		assert!(world.candidates().iter().all(|candidate| candidate.fitness() == pathological(candidate.position())));
+	}
+
+	#[test]
+	fn abort_policy_test() {
+		let world = slime::WorldState::new(10, objective(), 2, 50, 0.03, FitnessSettings::new().with_non_finite_policy(NonFinitePolicy::Abort), RandomSource::seed_from_u64(0));
+		assert_eq!(world.err(), Some(SlimeError::NonFiniteFitness));
+	}
+
+	#[test]
+	fn maximization_mirrors_minimization_test() {
+		let run = |direction: Direction, sign: f64| {
+			let objective = FnObjective::new(move |input: &VectorN<3>| sign * Functions::Rastrigin.calculate(input.as_slice()), Functions::Rastrigin.get_bounds(3));
+			let objective: Arc<dyn Objective<VectorN<3>>> = Arc::new(objective);
+			let mut world = MultiSwarm::<VectorN<3>, _>::new(3, 0.1, RandomSource::seed_from_u64(0), |rng_source| {
+				return slime::WorldState::new(10, objective.clone(), 3, 50, 0.03, FitnessSettings::new().with_direction(direction), rng_source);
+			}).unwrap();
+			return world.run().unwrap();
+		};
+		let minimized = run(Direction::Minimize, 1.0);
+		let maximized = run(Direction::Maximize, -1.0);
+
+		assert_eq!(minimized.best_solution, maximized.best_solution);
+		assert_eq!(minimized.best_value, -maximized.best_value);
+		for (record, next) in maximized.history.iter().zip(&maximized.history[1..]) {
+			assert!(next.best_value >= record.best_value);
+			assert!(record.best_value >= record.mean_fitness && record.mean_fitness >= record.worst_fitness);
+		}
+	}
+}
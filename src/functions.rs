@@ -1,73 +1,244 @@
-use std::f64::consts::{E, TAU};
+use std::f64::consts::{E, PI, TAU};
+use std::fmt::Display;
+use std::str::FromStr;
 
-use crate::vector::VectorN;
+use crate::bounds::Bounds;
+use crate::error::SlimeError;
+use crate::objective::{Objective, Optimum};
+use crate::vector::Vector;
 use crate::vector::QuickFold;
 
 // functions 1
-fn ackley<const N: usize>(input: VectorN<N>) -> f64 {
-	return -20.0 * (-0.2 * ((N as f64).recip() * input.coordinates.map(|a| a.powi(2)).sum()).sqrt()).exp() -
-		((N as f64).recip() * input.coordinates.map(|a| (TAU * a).cos()).sum()).exp() +
+fn ackley(input: &[f64]) -> f64 {
+	let n = input.len() as f64;
+	return -20.0 * (-0.2 * (n.recip() * input.iter().map(|a| a.powi(2)).sum::<f64>()).sqrt()).exp() -
+		(n.recip() * input.iter().map(|a| (TAU * a).cos()).sum::<f64>()).exp() +
 		E + 20.0;
 }
 
 // functions 1
-fn schwefel<const N: usize>(input: VectorN<N>) -> f64 {
-	let absolutes = input.coordinates.map(f64::abs);
-	return absolutes.map(|a| a.powi(2)).sum() + absolutes.product();
+fn schwefel(input: &[f64]) -> f64 {
+	let absolutes = input.iter().map(|a| a.abs()).collect::<Vec<_>>();
+	return absolutes.iter().map(|a| a.powi(2)).sum::<f64>() + absolutes.product();
 }
 
 // functions 1
-fn brown<const N: usize>(input: VectorN<N>) -> f64 {
-	return input.coordinates.map(|a| a.powi(2)).array_windows::<2>().map(|&[a, a_1]| {
+fn brown(input: &[f64]) -> f64 {
+	return input.iter().map(|a| a.powi(2)).collect::<Vec<_>>().windows(2).map(|window| {
+		let (a, a_1) = (window[0], window[1]);
 		return a.powf(a_1 + 1.0) + a_1.powf(a + 1.0);
 	}).sum();
 }
 
 // functions 2
-fn rastrigin<const N: usize>(input: VectorN<N>) -> f64 {
-	return input.coordinates.map(|a| {
+fn rastrigin(input: &[f64]) -> f64 {
+	return input.iter().map(|a| {
 		return a.powi(2) - 10.0 * (TAU * a).cos() + 10.0;
 	}).sum();
 }
 
 // functions 2
-fn schwefel2<const N: usize>(input: VectorN<N>) -> f64 {
-	return input.coordinates.map(|a| {
+fn schwefel2(input: &[f64]) -> f64 {
+	return input.iter().map(|a| {
 		return (a * a.abs().sqrt().sin()).abs();
 	}).sum();
 }
 
 // functions 2
-fn solomon<const N: usize>(input: VectorN<N>) -> f64 {
-	let sum_of_squares = input.coordinates.map(|a| a.powi(2)).sum();
+fn solomon(input: &[f64]) -> f64 {
+	let sum_of_squares = input.iter().map(|a| a.powi(2)).sum::<f64>();
 	return 1.0 - (TAU * sum_of_squares.sqrt()).cos() + 0.1 * sum_of_squares.sqrt();
 }
 
 
-#[derive(Debug, Clone, Copy)]
-pub enum Functions<const N: usize> {
+// extended benchmark library
+fn rosenbrock(input: &[f64]) -> f64 {
+	return input.windows(2).map(|window| {
+		return 100.0 * (window[1] - window[0].powi(2)).powi(2) + (window[0] - 1.0).powi(2);
+	}).sum();
+}
+
+fn sphere(input: &[f64]) -> f64 {
+	return input.iter().map(|a| a.powi(2)).sum();
+}
+
+fn griewank(input: &[f64]) -> f64 {
+	let sum = input.iter().map(|a| a.powi(2)).sum::<f64>() / 4000.0;
+	let product = input.iter().enumerate().map(|(index, a)| (a / ((index + 1) as f64).sqrt()).cos()).product::<f64>();
+	return sum - product + 1.0;
+}
+
+fn levy(input: &[f64]) -> f64 {
+	let w = input.iter().map(|a| 1.0 + (a - 1.0) / 4.0).collect::<Vec<_>>();
+	let last = w[w.len() - 1];
+	let middle = w[..w.len() - 1].iter().map(|w_i| {
+		return (w_i - 1.0).powi(2) * (1.0 + 10.0 * (PI * w_i + 1.0).sin().powi(2));
+	}).sum::<f64>();
+	return (PI * w[0]).sin().powi(2) + middle + (last - 1.0).powi(2) * (1.0 + (TAU * last).sin().powi(2));
+}
+
+fn zakharov(input: &[f64]) -> f64 {
+	let weighted_sum = input.iter().enumerate().map(|(index, a)| 0.5 * (index + 1) as f64 * a).sum::<f64>();
+	return input.iter().map(|a| a.powi(2)).sum::<f64>() + weighted_sum.powi(2) + weighted_sum.powi(4);
+}
+
+fn styblinski_tang(input: &[f64]) -> f64 {
+	return 0.5 * input.iter().map(|a| a.powi(4) - 16.0 * a.powi(2) + 5.0 * a).sum::<f64>();
+}
+
+fn michalewicz(input: &[f64]) -> f64 {
+	return -input.iter().enumerate().map(|(index, a)| {
+		return a.sin() * ((index + 1) as f64 * a.powi(2) / PI).sin().powi(20);
+	}).sum::<f64>();
+}
+
+fn dixon_price(input: &[f64]) -> f64 {
+	return (input[0] - 1.0).powi(2) + input.windows(2).enumerate().map(|(index, window)| {
+		return (index + 2) as f64 * (2.0 * window[1].powi(2) - window[0]).powi(2);
+	}).sum::<f64>();
+}
+
+fn alpine(input: &[f64]) -> f64 {
+	return input.iter().map(|a| (a * a.sin() + 0.1 * a).abs()).sum();
+}
+
+// expanded over consecutive pairs of coordinates
+fn schaffer_f6(input: &[f64]) -> f64 {
+	return input.windows(2).map(|window| {
+		let squares = window[0].powi(2) + window[1].powi(2);
+		return 0.5 + (squares.sqrt().sin().powi(2) - 0.5) / (1.0 + 0.001 * squares).powi(2);
+	}).sum();
+}
+
+fn schaffer_f7(input: &[f64]) -> f64 {
+	let pair_count = input.len().saturating_sub(1).max(1) as f64;
+	return (input.windows(2).map(|window| {
+		let s = (window[0].powi(2) + window[1].powi(2)).sqrt();
+		return s.sqrt() + s.sqrt() * (50.0 * s.powf(0.2)).sin().powi(2);
+	}).sum::<f64>() / pair_count).powi(2);
+}
+
+fn bent_cigar(input: &[f64]) -> f64 {
+	return input[0].powi(2) + 1e6 * input[1..].iter().map(|a| a.powi(2)).sum::<f64>();
+}
+
+fn discus(input: &[f64]) -> f64 {
+	return 1e6 * input[0].powi(2) + input[1..].iter().map(|a| a.powi(2)).sum::<f64>();
+}
+
+fn happy_cat(input: &[f64]) -> f64 {
+	let n = input.len() as f64;
+	let squared_norm = input.iter().map(|a| a.powi(2)).sum::<f64>();
+	return (squared_norm - n).abs().powf(0.25) + (0.5 * squared_norm + input.iter().sum::<f64>()) / n + 0.5;
+}
+
+// Per coordinate minimum of the Styblinski-Tang function, reached at the root of 4x^3 - 32x + 5
+const STYBLINSKI_TANG_ARGMIN: f64 = -2.903534027771178;
+const STYBLINSKI_TANG_MIN: f64 = -39.16616570377142;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Functions {
 	Ackley,
 	Schwefel,
 	Brown,
 	Rastrigin,
 	Schwefel2,
 	Solomon,
+	Rosenbrock,
+	Sphere,
+	Griewank,
+	Levy,
+	Zakharov,
+	StyblinskiTang,
+	Michalewicz,
+	DixonPrice,
+	Alpine,
+	SchafferF6,
+	SchafferF7,
+	BentCigar,
+	Discus,
+	HappyCat,
+}
+
+/// Canonical name, domain and known optimum of a benchmark function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FunctionInfo {
+	pub function: Functions,
+	pub name: &'static str,
+	pub range: (f64, f64),
 }
 
-impl<const N: usize> Functions<N> {
-	pub fn make_from_name(name: &str) -> Self {
-		match name {
-			"ackley" => return Self::Ackley,
-			"schwefel" => return Self::Schwefel,
-			"brown" => return Self::Brown,
-			"rastrigin" => return Self::Rastrigin,
-			"schwefel2" => return Self::Schwefel2,
-			"solomon" => return Self::Solomon,
-			_ => panic!("Nonexistent function passed: `{name}`"),
+impl FunctionInfo {
+	/// Lowest value of the function, which depends on the dimension count for some of them
+	pub fn optimum(&self, dimensions: usize) -> Option<f64> {
+		return self.function.optimum_value(dimensions);
+	}
+}
+
+impl Functions {
+	pub const ALL: [Functions; 20] = [
+		Self::Ackley,
+		Self::Schwefel,
+		Self::Brown,
+		Self::Rastrigin,
+		Self::Schwefel2,
+		Self::Solomon,
+		Self::Rosenbrock,
+		Self::Sphere,
+		Self::Griewank,
+		Self::Levy,
+		Self::Zakharov,
+		Self::StyblinskiTang,
+		Self::Michalewicz,
+		Self::DixonPrice,
+		Self::Alpine,
+		Self::SchafferF6,
+		Self::SchafferF7,
+		Self::BentCigar,
+		Self::Discus,
+		Self::HappyCat,
+	];
+
+	/// Information about every available function
+	pub fn registry() -> Vec<FunctionInfo> {
+		return Self::ALL.iter().map(|function| function.info()).collect();
+	}
+
+	pub fn info(self) -> FunctionInfo {
+		return FunctionInfo {
+			function: self,
+			name: self.name(),
+			range: self.get_range(),
+		};
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Ackley => return "ackley",
+			Self::Schwefel => return "schwefel",
+			Self::Brown => return "brown",
+			Self::Rastrigin => return "rastrigin",
+			Self::Schwefel2 => return "schwefel2",
+			Self::Solomon => return "solomon",
+			Self::Rosenbrock => return "rosenbrock",
+			Self::Sphere => return "sphere",
+			Self::Griewank => return "griewank",
+			Self::Levy => return "levy",
+			Self::Zakharov => return "zakharov",
+			Self::StyblinskiTang => return "styblinski-tang",
+			Self::Michalewicz => return "michalewicz",
+			Self::DixonPrice => return "dixon-price",
+			Self::Alpine => return "alpine",
+			Self::SchafferF6 => return "schaffer-f6",
+			Self::SchafferF7 => return "schaffer-f7",
+			Self::BentCigar => return "bent-cigar",
+			Self::Discus => return "discus",
+			Self::HappyCat => return "happy-cat",
 		}
 	}
 
-	pub fn get_bounds(self) -> (f64, f64) {
+	/// Range shared by every coordinate of the function's domain
+	pub fn get_range(self) -> (f64, f64) {
 		match self {
 			Self::Ackley => return (-32.0, 32.0),
 			Self::Schwefel => return (-10.0, 10.0),
@@ -75,10 +246,73 @@ impl<const N: usize> Functions<N> {
 			Self::Rastrigin => return (-5.12, 5.12),
 			Self::Schwefel2 => return (-100.0, 100.0),
 			Self::Solomon => return (-100.0, 100.0),
+			Self::Rosenbrock => return (-5.0, 10.0),
+			Self::Sphere => return (-5.12, 5.12),
+			Self::Griewank => return (-600.0, 600.0),
+			Self::Levy => return (-10.0, 10.0),
+			Self::Zakharov => return (-5.0, 10.0),
+			Self::StyblinskiTang => return (-5.0, 5.0),
+			Self::Michalewicz => return (0.0, PI),
+			Self::DixonPrice => return (-10.0, 10.0),
+			Self::Alpine => return (-10.0, 10.0),
+			Self::SchafferF6 | Self::SchafferF7 | Self::BentCigar | Self::Discus => return (-100.0, 100.0),
+			Self::HappyCat => return (-2.0, 2.0),
 		}
 	}
 
-	pub fn calculate(self, input: VectorN<N>) -> f64 {
+	/// Lowest value of the function, `None` if it is not known for the dimension count
+	pub fn optimum_value(self, dimensions: usize) -> Option<f64> {
+		match self {
+			Self::StyblinskiTang => return Some(STYBLINSKI_TANG_MIN * dimensions as f64),
+			Self::Michalewicz => match dimensions {
+				2 => return Some(-1.8013034100985537),
+				5 => return Some(-4.687658179),
+				10 => return Some(-9.66015171564134),
+				_ => return None,
+			},
+			_ => return Some(0.0),
+		}
+	}
+
+	/// Point at which the lowest value is reached, `None` if there are several of them or it is not known
+	pub fn optimum_location(self, dimensions: usize) -> Option<Vec<f64>> {
+		match self {
+			// zero wherever every coordinate is 0 or (k * pi)^2
+			Self::Schwefel2 => return None,
+			Self::Rosenbrock | Self::Levy => return Some(vec![1.0; dimensions]),
+			Self::HappyCat => return Some(vec![-1.0; dimensions]),
+			Self::StyblinskiTang => return Some(vec![STYBLINSKI_TANG_ARGMIN; dimensions]),
+			Self::Michalewicz => match dimensions {
+				2 => return Some(vec![2.202905520172606, PI / 2.0]),
+				_ => return None,
+			},
+			Self::DixonPrice => return Some((1..=dimensions).map(|i| {
+				let power = 2.0f64.powi(i as i32);
+				return 2.0f64.powf(-(power - 2.0) / power);
+			}).collect()),
+			_ => return Some(vec![0.0; dimensions]),
+		}
+	}
+
+	pub fn get_optimum<V: Vector>(self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.optimum_value(dimensions).map(|value| Optimum {
+			value,
+			location: self.optimum_location(dimensions).map(|coordinates| {
+				let mut location = V::zeros(dimensions);
+				for (entry, coordinate) in location.as_mut_slice().iter_mut().zip(coordinates) {
+					*entry = coordinate;
+				}
+				return location;
+			}),
+		});
+	}
+
+	pub fn get_bounds<V: Vector>(self, dimensions: usize) -> Bounds<V> {
+		let (lower, upper) = self.get_range();
+		return Bounds::uniform(dimensions, lower, upper);
+	}
+
+	pub fn calculate(self, input: &[f64]) -> f64 {
 		match self {
 			Functions::Ackley => return ackley(input),
 			Functions::Schwefel => return schwefel(input),
@@ -86,13 +320,115 @@ impl<const N: usize> Functions<N> {
 			Functions::Rastrigin => return rastrigin(input),
 			Functions::Schwefel2 => return schwefel2(input),
 			Functions::Solomon => return solomon(input),
+			Functions::Rosenbrock => return rosenbrock(input),
+			Functions::Sphere => return sphere(input),
+			Functions::Griewank => return griewank(input),
+			Functions::Levy => return levy(input),
+			Functions::Zakharov => return zakharov(input),
+			Functions::StyblinskiTang => return styblinski_tang(input),
+			Functions::Michalewicz => return michalewicz(input),
+			Functions::DixonPrice => return dixon_price(input),
+			Functions::Alpine => return alpine(input),
+			Functions::SchafferF6 => return schaffer_f6(input),
+			Functions::SchafferF7 => return schaffer_f7(input),
+			Functions::BentCigar => return bent_cigar(input),
+			Functions::Discus => return discus(input),
+			Functions::HappyCat => return happy_cat(input),
 		}
 	}
 }
 
-impl<const N: usize> FnOnce<(VectorN<N>,)> for Functions<N> {
+impl FromStr for Functions {
+	type Err = SlimeError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|&function| function.name() == name).ok_or_else(|| SlimeError::UnknownFunction(name.to_string()));
+	}
+}
+
+impl Display for Functions {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", Functions::name(*self));
+	}
+}
+
+impl<V: Vector> FnOnce<(V,)> for Functions {
 	type Output = f64;
-	extern "rust-call" fn call_once(self, args: (VectorN<N>,)) -> Self::Output {
-		return self.calculate(args.0);
+	extern "rust-call" fn call_once(self, args: (V,)) -> Self::Output {
+		return self.calculate(args.0.as_slice());
+	}
+}
+
+impl<V: Vector> Objective<V> for Functions {
+	fn value(&self, input: &V) -> f64 {
+		return self.calculate(input.as_slice());
 	}
-}
\ No newline at end of file
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		return self.get_bounds(dimensions);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some(Functions::name(*self));
+	}
+
+	fn optimum(&self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.get_optimum(dimensions);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use rand::SeedableRng;
+
+	use crate::{error::SlimeError, functions::Functions, objective::Objective, random::RandomSource, vector::{DynVector, VectorN}};
+
+	#[test]
+	fn parse_test() {
+		for function in Functions::ALL {
+			assert_eq!(function.name().parse::<Functions>(), Ok(function));
+		}
+		assert_eq!("rastrign".parse::<Functions>(), Err(SlimeError::UnknownFunction("rastrign".to_string())));
+	}
+
+	#[test]
+	fn registry_optimum_test() {
+		for function in Functions::ALL {
+			let info = Functions::info(function);
+			for dimensions in [2, 5, 10] {
+				let Some(optimum) = info.optimum(dimensions) else {
+					continue;
+				};
+				let Some(location) = function.optimum_location(dimensions).map(DynVector::new) else {
+					continue;
+				};
+				assert!(function.get_bounds(dimensions).contains(&location), "{} optimum is out of bounds", info.name);
+				let value = function.value(&location);
+				assert!((value - optimum).abs() < 1e-9, "{} ({}D) at {:?} is {}", info.name, dimensions, location, value);
+			}
+		}
+	}
+
+	#[test]
+	fn known_optima_test() {
+		assert_eq!(Functions::Schwefel2.value(&VectorN::new([0.0, 1e-300])), 0.0);
+		assert!(Functions::Michalewicz.get_optimum::<VectorN<5>>(5).is_some_and(|optimum| optimum.location.is_none()));
+		assert!(Functions::Michalewicz.get_optimum::<VectorN<3>>(3).is_none());
+		assert_eq!(Functions::StyblinskiTang.optimum_value(3), Some(-39.16616570377142 * 3.0));
+	}
+
+	#[test]
+	fn no_value_below_optimum_test() {
+		let mut random_source = RandomSource::seed_from_u64(0);
+		for function in Functions::ALL {
+			let Some(optimum) = function.get_optimum::<DynVector>(5) else {
+				continue;
+			};
+			let bounds = function.get_bounds::<DynVector>(5);
+			for _ in 0..1000 {
+				let point = bounds.sample(&mut random_source);
+				assert!(function.value(&point) >= optimum.value - 1e-9, "{} at {:?} is below its optimum", function, point);
+			}
+		}
+	}
+}
@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+
+use crate::{optimizer::Optimizer, vector::Vector};
+
+/// State of a run after a single iteration, used for plotting convergence curves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterationRecord {
+	pub iteration: usize,
+	/// Best value found since the start of the run
+	pub best_value: f64,
+	pub mean_fitness: f64,
+	pub worst_fitness: f64,
+	pub evaluations: usize,
+}
+
+impl IterationRecord {
+	pub fn from_optimizer<V: Vector>(optimizer: &(impl Optimizer<V> + ?Sized)) -> Self {
+		let candidates = optimizer.candidates();
+		let fitness_sum = candidates.iter().map(|candidate| candidate.fitness()).sum::<f64>();
+		let direction = optimizer.direction();
+		let worst_fitness = candidates.iter().map(|candidate| candidate.fitness()).max_by(|&first, &second| direction.compare(first, second)).unwrap_or(direction.worst());
+
+		return Self {
+			iteration: optimizer.current_iteration(),
+			best_value: optimizer.best_value(),
+			mean_fitness: fitness_sum / candidates.len() as f64,
+			worst_fitness,
+			evaluations: optimizer.evaluations(),
+		};
+	}
+}
+
+pub fn write_csv(history: &[IterationRecord], mut output: impl Write) -> io::Result<()> {
+	writeln!(output, "iteration,evaluations,best_value,mean_fitness,worst_fitness")?;
+	for record in history {
+		writeln!(output, "{},{},{},{},{}", record.iteration, record.evaluations, record.best_value, record.mean_fitness, record.worst_fitness)?;
+	}
+	return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, history::write_csv, multi_swarm::MultiSwarm, optimizer::Optimizer, particles, random::RandomSource, vector::VectorN};
+
+	#[test]
+	fn multi_swarm_history_test() {
+		let coefficients = particles::Coefficients { social: 0.6, cognitive: 0.5, inertia: 0.7 };
+		let mut world = MultiSwarm::<VectorN<4>, _>::new(3, 0.1, RandomSource::seed_from_u64(0), |rng_source| {
+			return particles::WorldState::new(10, Arc::new(Functions::Ackley), 4, 50, coefficients, FitnessSettings::new(), rng_source);
+		}).unwrap();
+		let result = world.run().unwrap();
+
+		assert_eq!(result.history.len(), 51);
+		assert_eq!(result.history[0].evaluations, 30);
+		assert_eq!(result.history[50].evaluations, 30 * 51);
+		for (record, next) in result.history.iter().zip(&result.history[1..]) {
+			assert!(next.best_value <= record.best_value);
+			assert!(record.best_value <= record.mean_fitness && record.mean_fitness <= record.worst_fitness);
+		}
+
+		let mut csv = Vec::new();
+		write_csv(&result.history, &mut csv).unwrap();
+		assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 52);
+	}
+}
@@ -1,10 +1,29 @@
-#![feature(generic_arg_infer)]
-#![feature(array_windows)]
 #![feature(unboxed_closures)]
 #![feature(fn_traits)]
 #![allow(clippy::needless_return)]
 
+pub mod error;
 pub mod functions;
+pub mod cec2017;
+pub mod engineering;
+pub mod objective;
+pub mod transform;
+pub mod noise;
+pub mod fitness;
+pub mod constraints;
+pub mod binary;
+pub mod permutation;
+pub mod tsplib;
+pub mod multi_objective;
+pub mod pareto;
 pub mod vector;
+pub mod bounds;
+pub mod random;
+pub mod optimizer;
+pub mod stopping;
+pub mod history;
+pub mod observer;
 pub mod slime;
-pub mod particles;
\ No newline at end of file
+pub mod particles;
+pub mod multi_swarm;
+pub mod mosma;
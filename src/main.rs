@@ -1,11 +1,13 @@
+#![allow(clippy::needless_return)]
+
 mod batch;
 
-use batch::BatchRunData;
 use clap::{Args, Parser, Subcommand};
-use rand::thread_rng;
-use slimes::{functions::Functions, particles, slime};
+use rand::{thread_rng, Rng, SeedableRng};
+use std::{fs::File, io::BufWriter, path::{Path, PathBuf}, str::FromStr, sync::Arc, time::Duration};
 
-const FN_SIZE: usize = 5;
+use batch::BatchSummary;
+use slimes::{cec2017::{Cec2017, Cec2017Data}, constraints::{ConstraintHandling, DEFAULT_EQUALITY_TOLERANCE, DEFAULT_PENALTY_COEFFICIENT}, engineering::EngineeringProblem, error::SlimeError, fitness::{Direction, FitnessSettings, NonFinitePolicy}, functions::Functions, history::{self, IterationRecord}, multi_swarm::MultiSwarm, noise::{NoiseModel, Noisy}, objective::Objective, optimizer::Optimizer, particles, permutation::RandomKeys, random::{self, RandomSource}, slime, stopping::{self, StoppingCriteria}, transform::{Rotated, Scaled, Shifted}, tsplib::Tsp, vector::{DynVector, Vector, VectorN}};
 
 
 #[derive(Debug, Parser, Clone)]
@@ -13,13 +15,61 @@ struct Config {
     #[command(flatten)]
     multi_swarm_config: Option<MultiSwarmConfig>,
     #[arg(long = "functions", value_delimiter = ',', num_args = 1.., required = true)]
-    functions: Vec<String>,
+    functions: Vec<Problem>,
+    /// Directory with the official CEC 2017 data files, generated shifts and rotations are used if not given
+    #[arg(long = "cec-data")]
+    cec_data: Option<PathBuf>,
+    /// Directory with the TSPLIB instances selected as tsplib-<name>, the working directory if not given
+    #[arg(long = "tsplib-dir")]
+    tsplib_dir: Option<PathBuf>,
+    #[command(flatten)]
+    transform_config: TransformConfig,
+    #[command(flatten)]
+    noise_config: NoiseConfig,
+    /// Additional evaluations averaged into the value of the best solution, for noisy functions
+    #[arg(long = "reevaluations", default_value_t = 0)]
+    reevaluations: usize,
+    /// Ranking of solutions violating the constraints: static-penalty, adaptive-penalty, feasibility-rules or epsilon
+    #[arg(long = "constraint-handling", default_value_t = ConstraintHandling::FeasibilityRules)]
+    constraint_handling: ConstraintHandling,
+    /// Initial coefficient of the penalty methods
+    #[arg(long = "penalty-coefficient", default_value_t = DEFAULT_PENALTY_COEFFICIENT)]
+    penalty_coefficient: f64,
+    /// Largest deviation from zero with which an equality constraint counts as satisfied
+    #[arg(long = "equality-tolerance", default_value_t = DEFAULT_EQUALITY_TOLERANCE)]
+    equality_tolerance: f64,
+    #[arg(long = "dimensions", value_delimiter = ',', num_args = 1.., default_value = "5")]
+    dimensions: Vec<usize>,
     #[arg(long = "try-count")]
     try_count: Option<usize>,
-    #[arg(long = "iterations")]
-    iterations: usize,
+    /// Number of threads executing the runs of a batch, defaults to the number of CPUs
+    #[arg(long = "threads", default_value_t = num_cpus::get())]
+    threads: usize,
+    #[arg(long = "iterations", required_unless_present = "evaluation-budget")]
+    iterations: Option<usize>,
+    /// Number of function evaluations given to every run. The iteration count is derived from it,
+    /// so single and multi-swarm variants of every algorithm get the same budget
+    #[arg(long = "evaluation-budget", name = "evaluation-budget", conflicts_with = "iterations")]
+    evaluation_budget: Option<usize>,
     #[arg(long = "pop-size")]
     population_size: usize,
+    /// Handling of NaN and infinite objective values: worst, resample or abort
+    #[arg(long = "non-finite-policy", default_value_t = NonFinitePolicy::Worst)]
+    non_finite_policy: NonFinitePolicy,
+    /// Whether to minimize or maximize the functions, reported values are never negated
+    #[arg(long = "direction", default_value_t = Direction::Minimize)]
+    direction: Direction,
+    /// Seed of the random number generator, chosen randomly and printed if not given
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+    #[command(flatten)]
+    stopping_config: StoppingConfig,
+    /// Largest error `f(x) - f*` with which a run of a batch counts as successful
+    #[arg(long = "success-tolerance", default_value_t = 1e-8)]
+    success_tolerance: f64,
+    /// Directory receiving a CSV file with the convergence history of every run
+    #[arg(long = "history-dir")]
+    history_dir: Option<PathBuf>,
 
     #[command(subcommand)]
     variant: OptimizationVariant
@@ -33,6 +83,117 @@ struct MultiSwarmConfig {
     swarm_count: usize,
 }
 
+impl Config {
+    fn iteration_count(&self) -> usize {
+        if let Some(budget) = self.evaluation_budget {
+            let swarm_count = self.multi_swarm_config.map_or(1, |swarm_config| swarm_config.swarm_count);
+            return stopping::iterations_for_budget(budget, self.population_size * swarm_count);
+        }
+        return self.iterations.unwrap();
+    }
+
+    fn fitness_settings(&self) -> FitnessSettings {
+        return FitnessSettings::new().with_direction(self.direction).with_non_finite_policy(self.non_finite_policy).with_reevaluations(self.reevaluations)
+            .with_constraint_handling(self.constraint_handling).with_penalty_coefficient(self.penalty_coefficient).with_equality_tolerance(self.equality_tolerance);
+    }
+
+    fn stopping_criteria(&self) -> StoppingCriteria {
+        let mut criteria = self.stopping_config.criteria();
+        if let Some(budget) = self.evaluation_budget {
+            criteria.max_evaluations = Some(criteria.max_evaluations.map_or(budget, |max| max.min(budget)));
+        }
+        return criteria;
+    }
+}
+
+/// Additional conditions ending a run before the iteration count is reached
+#[derive(Debug, Args, Clone, Copy)]
+struct StoppingConfig {
+    #[arg(long = "max-evaluations")]
+    max_evaluations: Option<usize>,
+    /// Stop once the best value is within the target tolerance of this value
+    #[arg(long = "target-value")]
+    target_value: Option<f64>,
+    #[arg(long = "target-tolerance", default_value_t = 1e-8)]
+    target_tolerance: f64,
+    /// Stop after this many iterations without improvement
+    #[arg(long = "stagnation-iterations")]
+    stagnation_iterations: Option<usize>,
+    /// Stop once the mean distance of the population to its centroid drops below this value
+    #[arg(long = "min-diversity")]
+    min_diversity: Option<f64>,
+    /// Wall clock limit of a single run, in seconds
+    #[arg(long = "time-limit")]
+    time_limit: Option<f64>,
+}
+
+impl StoppingConfig {
+    fn criteria(&self) -> StoppingCriteria {
+        return StoppingCriteria {
+            max_evaluations: self.max_evaluations,
+            target: self.target_value.map(|target| (target, self.target_tolerance)),
+            stagnation_iterations: self.stagnation_iterations,
+            min_diversity: self.min_diversity,
+            time_limit: self.time_limit.map(Duration::from_secs_f64),
+        };
+    }
+}
+
+/// Transformations applied to every function, so optima away from the origin and non-separable problems can be tested
+#[derive(Debug, Args, Clone, Copy)]
+struct TransformConfig {
+    /// Multiply the coordinates by this factor before evaluating, dividing the bounds by it
+    #[arg(long = "scale")]
+    scale: Option<f64>,
+    /// Rotate the functions around their optimum by a random orthogonal matrix
+    #[arg(long = "rotate")]
+    rotate: bool,
+    /// Move the optimum of the functions to a random point inside the bounds
+    #[arg(long = "shift")]
+    shift: bool,
+    /// Seed of the random shifts and rotations, so every run and every algorithm gets the same problem instance
+    #[arg(long = "transform-seed", default_value_t = 0)]
+    transform_seed: u64,
+}
+
+impl TransformConfig {
+    /// Wraps the objective in the transformations, scaling first and shifting last
+    fn apply<V: Vector>(&self, mut objective: Arc<dyn Objective<V>>, dimensions: usize) -> Result<Arc<dyn Objective<V>>, SlimeError> {
+        let mut random_source = random::run_source(self.transform_seed, dimensions);
+        if let Some(factor) = self.scale {
+            objective = Arc::new(Scaled::uniform(objective, dimensions, factor)?);
+        }
+        if self.rotate {
+            objective = Arc::new(Rotated::random(objective, dimensions, &mut random_source));
+        }
+        if self.shift {
+            objective = Arc::new(Shifted::random(objective, dimensions, &mut random_source));
+        }
+        return Ok(objective);
+    }
+}
+
+/// Gaussian noise added to every evaluation
+#[derive(Debug, Args, Clone, Copy)]
+struct NoiseConfig {
+    /// Standard deviation of the noise, the functions are noise-free if not given
+    #[arg(long = "noise-sigma")]
+    noise_sigma: Option<f64>,
+    /// Whether the noise is added to the value or scales it: additive or multiplicative
+    #[arg(long = "noise-model", default_value_t = NoiseModel::Additive)]
+    noise_model: NoiseModel,
+}
+
+impl NoiseConfig {
+    /// Wraps the objective of a single run, drawing the noise generator from the generator of the run
+    fn apply<V: Vector>(&self, objective: Arc<dyn Objective<V>>, rng_source: &mut RandomSource) -> Result<Arc<dyn Objective<V>>, SlimeError> {
+        let Some(sigma) = self.noise_sigma else {
+            return Ok(objective);
+        };
+        return Ok(Arc::new(Noisy::new(objective, self.noise_model, sigma, RandomSource::from_rng(rng_source).unwrap())?));
+    }
+}
+
 #[derive(Debug, Clone, Subcommand, Copy)]
 enum OptimizationVariant {
     Particles(ParticleConfig),
@@ -53,109 +214,234 @@ struct ParticleConfig {
     inertia_coeff: f64,
 }
 
-fn main() {
-    let config = Config::parse();
+/// Benchmark selected on the command line
+#[derive(Debug, Clone)]
+enum Problem {
+    Function(Functions),
+    Cec2017(usize),
+    Engineering(EngineeringProblem),
+    /// TSPLIB instance, solved through random keys with one dimension per city whatever `--dimensions` says.
+    /// Only the name is known when parsing, the instance is read once by `load`
+    Tsplib { instance: String, tsp: Option<Arc<Tsp>> },
+}
 
-    if config.functions.is_empty() {
-        panic!("No functions given");
+impl Problem {
+    fn name(&self) -> String {
+        match *self {
+            Self::Function(function) => return function.name().to_string(),
+            Self::Cec2017(number) => return format!("cec2017-f{}", number),
+            Self::Engineering(problem) => return problem.name().to_string(),
+            Self::Tsplib { ref instance, .. } => return format!("tsplib-{}", instance),
+        }
+    }
+
+    /// Reads the data of problems stored in files, which are looked up in directories given by other arguments
+    fn load(&mut self, tsplib_dir: Option<&Path>) -> Result<(), SlimeError> {
+        if let Self::Tsplib { instance, tsp } = self {
+            let path = tsplib_dir.unwrap_or(Path::new(".")).join(format!("{}.tsp", instance));
+            *tsp = Some(Arc::new(Tsp::load(&path)?));
+        }
+        return Ok(());
+    }
+
+    /// Number of variables of problems that are only defined for one dimension count, these ignore `--dimensions`
+    fn fixed_dimensions(&self) -> Option<usize> {
+        match *self {
+            Self::Engineering(problem) => return Some(problem.dimensions()),
+            Self::Tsplib { ref tsp, .. } => return Some(loaded(tsp).size()),
+            _ => return None,
+        }
+    }
+
+    fn objective<V: Vector>(&self, dimensions: usize, cec_data: Option<&Path>) -> Result<Arc<dyn Objective<V>>, SlimeError> {
+        match *self {
+            Self::Function(function) => return Ok(Arc::new(function)),
+            Self::Cec2017(number) => {
+                let function = match cec_data {
+                    Some(directory) => Cec2017::with_data(number, dimensions, Cec2017Data::load(directory, number, dimensions)?)?,
+                    None => Cec2017::new(number, dimensions)?,
+                };
+                return Ok(Arc::new(function));
+            }
+            Self::Engineering(problem) => {
+                if dimensions != problem.dimensions() {
+                    return Err(SlimeError::UnsupportedDimensions { problem: problem.name().to_string(), dimensions });
+                }
+                return Ok(Arc::new(problem));
+            }
+            Self::Tsplib { ref tsp, .. } => return Ok(Arc::new(RandomKeys::new(loaded(tsp).clone()))),
+        }
+    }
+}
+
+fn loaded(tsp: &Option<Arc<Tsp>>) -> &Arc<Tsp> {
+    return tsp.as_ref().expect("TSPLIB instances are loaded before running");
+}
+
+/// Benchmarks selected by a prefixed name, listed after the library ones when a name is not recognized
+const PREFIXED_PROBLEMS: &str = "cec2017-f1 to cec2017-f30 except cec2017-f2 and tsplib-<instance>";
+
+impl FromStr for Problem {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let unknown = |error: SlimeError| format!("{}, {}", error, PREFIXED_PROBLEMS);
+        if let Some(number) = name.strip_prefix("cec2017-f") {
+            return number.parse().map(Self::Cec2017).map_err(|_| unknown(SlimeError::UnknownFunction(name.to_string())));
+        }
+        if let Some(instance) = name.strip_prefix("tsplib-").filter(|instance| !instance.is_empty()) {
+            return Ok(Self::Tsplib { instance: instance.to_string(), tsp: None });
+        }
+        if let Ok(problem) = name.parse() {
+            return Ok(Self::Engineering(problem));
+        }
+        return name.parse().map(Self::Function).map_err(unknown);
+    }
+}
+
+fn make_optimizer<V: Vector>(config: &Config, function: Arc<dyn Objective<V>>, dimensions: usize, rng_source: RandomSource) -> Result<Box<dyn Optimizer<V>>, SlimeError> {
+    if let Some(MultiSwarmConfig { migration_threshold, swarm_count }) = config.multi_swarm_config {
+        match config.variant {
+            OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
+                let coefficients = particles::Coefficients { social: social_coeff, cognitive: cognitive_coeff, inertia: inertia_coeff };
+                return Ok(Box::new(MultiSwarm::new(swarm_count, migration_threshold, rng_source, |swarm_rng| {
+                    return particles::WorldState::new(config.population_size, function.clone(), dimensions, config.iteration_count(), coefficients, config.fitness_settings(), swarm_rng);
+                })?));
+            }
+            OptimizationVariant::Slime { z_param } => {
+                return Ok(Box::new(MultiSwarm::new(swarm_count, migration_threshold, rng_source, |swarm_rng| {
+                    return slime::WorldState::new(config.population_size, function.clone(), dimensions, config.iteration_count(), z_param, config.fitness_settings(), swarm_rng);
+                })?));
+            }
+        }
+    } else {
+        match config.variant {
+            OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
+                let coefficients = particles::Coefficients { social: social_coeff, cognitive: cognitive_coeff, inertia: inertia_coeff };
+                return Ok(Box::new(particles::WorldState::new(config.population_size, function, dimensions, config.iteration_count(), coefficients, config.fitness_settings(), rng_source)?));
+            }
+            OptimizationVariant::Slime { z_param } => {
+                return Ok(Box::new(slime::WorldState::new(config.population_size, function, dimensions, config.iteration_count(), z_param, config.fitness_settings(), rng_source)?));
+            }
+        }
+    }
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    return value.map_or_else(|| "n/a".to_string(), |value| value.to_string());
+}
+
+fn save_history(directory: &Path, file_name: &str, history: &[IterationRecord]) {
+    let file = File::create(directory.join(file_name)).unwrap_or_else(|error| panic!("Cannot create history file `{file_name}`: {error}"));
+    history::write_csv(history, BufWriter::new(file)).unwrap();
+}
+
+fn run_with_dimensions<V: Vector>(config: &Config, problems: &[Problem], dimensions: usize, seed: u64) -> Result<(), SlimeError> {
+    let test_functions = problems.iter().map(|problem| {
+        let objective = config.transform_config.apply(problem.objective::<V>(dimensions, config.cec_data.as_deref())?, dimensions)?;
+        let name = objective.name().map_or_else(|| problem.name(), str::to_string);
+        return Ok((objective, name));
+    }).collect::<Result<Vec<_>, SlimeError>>()?;
+
+    // construct one optimizer up front, so invalid settings are reported before any run starts
+    for (function, _) in &test_functions {
+        let mut rng_source = random::run_source(seed, 0);
+        make_optimizer(config, config.noise_config.apply(function.clone(), &mut rng_source)?, dimensions, rng_source)?;
     }
-    let test_functions = config.functions.into_iter().map(|s| {
-        return (Functions::<FN_SIZE>::make_from_name(&s), s);
-    }).collect::<Vec<_>>();
 
     if let Some(tries) = config.try_count {
         for (function, function_name) in test_functions {
-            let bounds = function.get_bounds();
-            let tries_per_thread = tries.div_ceil(num_cpus::get());
-            let mut threads = Vec::with_capacity(num_cpus::get());
-            for _ in 0..num_cpus::get() {
-                threads.push(std::thread::spawn(move || {
-                    let mut thread_stats = BatchRunData::new();
-                    if let Some(MultiSwarmConfig { migration_threshold, swarm_count }) = config.multi_swarm_config {
-                        match config.variant {
-                            OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
-                                let mut world = particles::MultiSwarmWorldState::new(swarm_count, migration_threshold, config.population_size, function, bounds, social_coeff, cognitive_coeff, inertia_coeff);
-                                for _ in 0..tries_per_thread {
-                                    world.do_all_iters(config.iterations);
-                                    thread_stats += world.best_solution_value;
-                                    world.reset();
-                                }
-                            }
-                            OptimizationVariant::Slime { z_param } => {
-                                let mut world = slime::MultiSwarmWorldState::new(swarm_count, migration_threshold, config.population_size, bounds, config.iterations, function, z_param);
-                                for _ in 0..tries_per_thread {
-                                    world.do_all_iters();
-                                    thread_stats += world.best_solution_value;
-                                    world.reset();
-                                }
-                            }
-                        }
-                    } else {
-                        match config.variant {
-                            OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
-                                let mut world = particles::WorldState::new(config.population_size, function, bounds, social_coeff, cognitive_coeff, inertia_coeff);
-                                for _ in 0..tries_per_thread {
-                                    world.do_all_iterations(config.iterations);
-                                    thread_stats += world.best_solution_value;
-                                    world.reset();
-                                }
-                            }
-                            OptimizationVariant::Slime { z_param } => {
-                                let mut world = slime::WorldState::new(config.population_size, bounds, config.iterations, function, z_param, thread_rng());
-                                for _ in 0..tries_per_thread {
-                                    world.do_all_iters();
-                                    thread_stats += world.best_solution_value;
-                                    world.reset();
-                                }
-                            }
-                        }
-                    }
-                    return thread_stats;
-                }));
-            }
-            let result = threads.into_iter().map(|handle| handle.join().unwrap()).reduce(|mut a, b| {
-				a += b;
-				return a;
-			}).unwrap();
-            println!("{}: Finished {} runs. Max solution is {}. Average solution is {}. Min solution is {}.", function_name, result.run_count, result.max_result, result.average, result.min_result);
+            let results = batch::run_batch(tries, config.threads, |run_index| {
+                let mut rng_source = random::run_source(seed, run_index);
+                let mut world = make_optimizer(config, config.noise_config.apply(function.clone(), &mut rng_source)?, dimensions, rng_source)?;
+                let result = world.run_until(&config.stopping_criteria())?;
+                if let Some(directory) = &config.history_dir {
+                    save_history(directory, &format!("{}_{}D_run{}.csv", function_name, dimensions, run_index), &result.history);
+                }
+                return Ok((result.best_value, result.best_solution, result.best_violation));
+            })?;
+            // the known optimum is a minimum, so errors are only meaningful when minimizing
+            let optimum = function.optimum(dimensions).filter(|_| config.direction == Direction::Minimize);
+            let BatchSummary { values, average_error, average_distance, success_rate, feasibility_rate } = batch::summarize(&results, optimum.as_ref(), config.success_tolerance);
+
+            println!(
+                "{} ({}D): Finished {} runs. Max solution is {}. Average solution is {}. Min solution is {}. Average error is {}. Average distance to optimum is {}. Success rate is {}. Feasibility rate is {}.",
+                function_name, dimensions, values.run_count, values.max_result, values.average, values.min_result,
+                format_optional(average_error), format_optional(average_distance), format_optional(success_rate), feasibility_rate
+            );
         }
     } else {
         let mut threads = Vec::new();
-		for (function, function_name) in test_functions {
-            let bounds = function.get_bounds();
+		for (function_index, (function, function_name)) in test_functions.into_iter().enumerate() {
+            let config = config.clone();
             threads.push(std::thread::spawn(move || {
-                if let Some(MultiSwarmConfig { migration_threshold, swarm_count }) = config.multi_swarm_config {
-                    match config.variant {
-                        OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
-                            let mut world = particles::MultiSwarmWorldState::new(swarm_count, migration_threshold, config.population_size, function, bounds, social_coeff, cognitive_coeff, inertia_coeff);
-                            world.do_all_iters(config.iterations);
-                            println!("{}: Found optimum at {:?} = {}", function_name, world.best_solution.coordinates, world.best_solution_value);
-                        }
-                        OptimizationVariant::Slime { z_param } => {
-                            let mut world = slime::MultiSwarmWorldState::new(swarm_count, migration_threshold, config.population_size, bounds, config.iterations, function, z_param);
-                            world.do_all_iters();
-                            println!("{}: Found optimum at {:?} = {}", function_name, world.best_solution.coordinates, world.best_solution_value);
-                        }
-                    }
-                } else {
-                    match config.variant {
-                        OptimizationVariant::Particles(ParticleConfig { social_coeff, cognitive_coeff, inertia_coeff }) => {
-                            let mut world = particles::WorldState::new(config.population_size, function, bounds, social_coeff, cognitive_coeff, inertia_coeff);
-                            world.do_all_iterations(config.iterations);
-                            println!("{}: Found optimum at {:?} = {}", function_name, world.best_solution.coordinates, world.best_solution_value);
-                        }
-                        OptimizationVariant::Slime { z_param } => {
-                            let mut world = slime::WorldState::new(config.population_size, bounds, config.iterations, function, z_param, thread_rng());
-                            world.do_all_iters();
-                            println!("{}: Found optimum at {:?} = {}", function_name, world.best_solution.coordinates, world.best_solution_value);
-                        }
-                    }
+                let mut rng_source = random::run_source(seed, function_index);
+                let mut world = make_optimizer(&config, config.noise_config.apply(function, &mut rng_source)?, dimensions, rng_source)?;
+                let result = world.run_until(&config.stopping_criteria())?;
+                if let Some(directory) = &config.history_dir {
+                    save_history(directory, &format!("{}_{}D.csv", function_name, dimensions), &result.history);
                 }
+                println!("{} ({}D): Found optimum at {:?} = {} with violation {} (stopped by {} after {} iterations and {} evaluations)", function_name, dimensions, result.best_solution.as_slice(), result.best_value, result.best_violation, result.termination_reason, result.iterations, result.evaluations);
+                return Ok(());
             }));
         }
 
         for thread in threads {
-            thread.join().unwrap();
+            thread.join().unwrap()?;
         }
     }
+    return Ok(());
 }
+
+fn main() {
+    let mut config = Config::parse();
+
+    if config.functions.is_empty() {
+        panic!("No functions given");
+    }
+
+    if let Some(directory) = &config.history_dir {
+        std::fs::create_dir_all(directory).unwrap_or_else(|error| panic!("Cannot create history directory: {error}"));
+    }
+
+    let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+    eprintln!("Seed: {}", seed);
+
+    let mut scalable = Vec::new();
+    let mut fixed = Vec::new();
+    for problem in &mut config.functions {
+        if let Err(error) = problem.load(config.tsplib_dir.as_deref()) {
+            eprintln!("Error ({}): {}", problem.name(), error);
+            std::process::exit(1);
+        }
+        match problem.fixed_dimensions() {
+            Some(dimensions) => fixed.push((problem.clone(), dimensions)),
+            None => scalable.push(problem.clone()),
+        }
+    }
+    let check = |result: Result<(), SlimeError>, dimensions: usize| {
+        if let Err(error) = result {
+            eprintln!("Error ({}D): {}", dimensions, error);
+            std::process::exit(1);
+        }
+    };
+
+    for &dimensions in config.dimensions.iter().filter(|_| !scalable.is_empty()) {
+        // common sizes get a stack allocated vector, everything else falls back to the heap
+        let result = match dimensions {
+            2 => run_with_dimensions::<VectorN<2>>(&config, &scalable, dimensions, seed),
+            5 => run_with_dimensions::<VectorN<5>>(&config, &scalable, dimensions, seed),
+            10 => run_with_dimensions::<VectorN<10>>(&config, &scalable, dimensions, seed),
+            20 => run_with_dimensions::<VectorN<20>>(&config, &scalable, dimensions, seed),
+            30 => run_with_dimensions::<VectorN<30>>(&config, &scalable, dimensions, seed),
+            50 => run_with_dimensions::<VectorN<50>>(&config, &scalable, dimensions, seed),
+            100 => run_with_dimensions::<VectorN<100>>(&config, &scalable, dimensions, seed),
+            _ => run_with_dimensions::<DynVector>(&config, &scalable, dimensions, seed),
+        };
+        check(result, dimensions);
+    }
+    // problems with a fixed number of variables run once at their own size
+    for (problem, dimensions) in fixed {
+        check(run_with_dimensions::<DynVector>(&config, &[problem], dimensions, seed), dimensions);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,275 @@
+use rand::prelude::*;
+
+use std::sync::Arc;
+
+use crate::{bounds::Bounds, error::{self, SlimeError}, multi_objective::MultiObjective, pareto::{self, Archive, Solution}, random::RandomSource, slime, vector::Vector};
+
+/// Mold of the multi-objective variant, carrying all objective values instead of a single fitness
+#[derive(Debug, Clone)]
+pub struct MultiSlime<V: Vector> {
+	position: V,
+	weight: f64,
+	values: Vec<f64>,
+}
+
+impl<V: Vector> MultiSlime<V> {
+	pub fn position(&self) -> &V {
+		return &self.position;
+	}
+
+	pub fn values(&self) -> &[f64] {
+		return &self.values;
+	}
+}
+
+impl<V: Vector> AsRef<[f64]> for MultiSlime<V> {
+	fn as_ref(&self) -> &[f64] {
+		return &self.values;
+	}
+}
+
+/// Multi-objective slime mould algorithm (MOSMA). The molds move by the rules of the single objective algorithm,
+/// with a leader drawn from an external archive of non-dominated solutions taking the place of the best solution.
+/// Survivors are chosen from the molds and their moved copies by non-dominated sorting and crowding distance.
+/// Every coordinate of a mold is moved by the same factor, so the spread can be lost on problems where a single
+/// boundary point dominates almost the whole search space, such as ZDT2
+#[derive(Clone)]
+pub struct WorldState<V: Vector> {
+	population: Vec<MultiSlime<V>>,
+	objective: Arc<dyn MultiObjective<V>>,
+	function_bounds: Bounds<V>,
+	archive: Archive<V>,
+	/// Ranking keys of the molds, the front index increased by less than one for crowded molds. Lower is better
+	keys: Vec<f64>,
+	z_parameter: f64,
+	a_parameter: f64,
+	iteration_count: usize,
+	current_iteration: usize,
+	evaluations: usize,
+	random_source: RandomSource,
+}
+
+impl<V: Vector> WorldState<V> {
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(pop_size: usize, objective: Arc<dyn MultiObjective<V>>, dimensions: usize, iteration_count: usize, z_parameter: f64, archive_size: usize, rng_source: RandomSource) -> Result<Self, SlimeError> {
+		let function_bounds = objective.bounds(dimensions);
+		error::validate_population(pop_size, iteration_count, &function_bounds, dimensions)?;
+		if !(0.0..=1.0).contains(&z_parameter) {
+			return Err(SlimeError::InvalidZParameter(z_parameter));
+		}
+		if archive_size == 0 {
+			return Err(SlimeError::InvalidArchiveSize(archive_size));
+		}
+		if dimensions < objective.objective_count() {
+			let problem = objective.name().unwrap_or("multi-objective problem").to_string();
+			return Err(SlimeError::UnsupportedDimensions { problem, dimensions });
+		}
+
+		let mut result = Self {
+			population: Vec::with_capacity(pop_size),
+			objective, function_bounds, z_parameter, iteration_count,
+			archive: Archive::new(archive_size),
+			keys: Vec::new(),
+			a_parameter: 0.0,
+			current_iteration: 0,
+			evaluations: 0,
+			random_source: rng_source,
+		};
+		result.start(pop_size)?;
+
+		return Ok(result);
+	}
+
+	/// Samples a new population and fills the archive with it
+	fn start(&mut self, pop_size: usize) -> Result<(), SlimeError> {
+		self.population.clear();
+		self.archive.clear();
+		for _ in 0..pop_size {
+			let position = self.function_bounds.sample(&mut self.random_source);
+			let values = self.evaluate(&position)?;
+			self.population.push(MultiSlime { position, values, weight: 0.0 });
+		}
+		for mold in &self.population {
+			self.archive.insert(Solution { position: mold.position.clone(), values: mold.values.clone() });
+		}
+		self.keys = ranking_keys(&self.population);
+		self.recalculate_a(0);
+		self.recalculate_weights();
+		return Ok(());
+	}
+
+	fn evaluate(&mut self, position: &V) -> Result<Vec<f64>, SlimeError> {
+		self.evaluations += 1;
+		let values = self.objective.values(position);
+		if values.iter().any(|value| !value.is_finite()) {
+			return Err(SlimeError::NonFiniteFitness);
+		}
+		return Ok(values);
+	}
+
+	fn recalculate_a(&mut self, iteration: usize) {
+		// add one because original code uses matlab, with 1 as index start
+		self.a_parameter = (-((iteration + 1) as f64 / self.iteration_count as f64) + 1.0).atanh();
+	}
+
+	fn recalculate_weights(&mut self) {
+		for (mold, weight) in self.population.iter_mut().zip(slime::weights(&self.keys, &mut self.random_source)) {
+			mold.weight = weight;
+		}
+	}
+
+	/// Binary tournament between archive members, preferring the less crowded one to spread the front
+	fn choose_leader(&mut self, crowding: &[f64]) -> Solution<V> {
+		let first = self.random_source.gen_range(0..crowding.len());
+		let second = self.random_source.gen_range(0..crowding.len());
+		let winner = if crowding[second] > crowding[first] { second } else { first };
+		return self.archive.members()[winner].clone();
+	}
+
+	fn do_iteration(&mut self, iter_number: usize) -> Result<(), SlimeError> {
+		let iter_progress = iter_number as f64 / self.iteration_count as f64;
+		let indices = (0..self.archive.members().len()).collect::<Vec<_>>();
+		let crowding = pareto::crowding_distance(self.archive.members(), &indices);
+		let parents = self.population.clone();
+		let mut offspring = parents.clone();
+		for mold in offspring.iter_mut() {
+			let leader = self.choose_leader(&crowding);
+			let partner = parents.choose(&mut self.random_source).unwrap();
+			// the distance in the objective space takes the place of the fitness gap to the best solution,
+			// and molds contract towards the leader instead of the origin, which would pull the whole front to a single point
+			let gap = mold.values.iter().zip(&leader.values).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt();
+			slime::move_position(&mut mold.position, &self.function_bounds, mold.weight, self.z_parameter, self.a_parameter, &leader.position, &partner.position, Some(&leader.position), gap, iter_progress, &mut self.random_source);
+			mold.values = self.evaluate(&mold.position)?;
+		}
+		for mold in &offspring {
+			self.archive.insert(Solution { position: mold.position.clone(), values: mold.values.clone() });
+		}
+
+		let pop_size = parents.len();
+		let mut combined = parents;
+		combined.append(&mut offspring);
+		self.population = select(combined, pop_size);
+		self.keys = ranking_keys(&self.population);
+		self.recalculate_a(iter_number);
+		self.recalculate_weights();
+		return Ok(());
+	}
+
+	/// Performs a single iteration of the algorithm
+	pub fn step(&mut self) -> Result<(), SlimeError> {
+		self.do_iteration(self.current_iteration)?;
+		self.current_iteration += 1;
+		return Ok(());
+	}
+
+	/// Performs all remaining iterations, returning the final archive
+	pub fn run(&mut self) -> Result<&[Solution<V>], SlimeError> {
+		while self.current_iteration < self.iteration_count {
+			self.step()?;
+		}
+		return Ok(self.archive.members());
+	}
+
+	/// Reinitializes the population and empties the archive, leaving the optimizer as if it was freshly constructed
+	pub fn reset(&mut self) -> Result<(), SlimeError> {
+		self.evaluations = 0;
+		self.current_iteration = 0;
+		return self.start(self.population.len());
+	}
+
+	pub fn current_iteration(&self) -> usize {
+		return self.current_iteration;
+	}
+
+	pub fn iteration_count(&self) -> usize {
+		return self.iteration_count;
+	}
+
+	pub fn evaluations(&self) -> usize {
+		return self.evaluations;
+	}
+
+	pub fn population(&self) -> &[MultiSlime<V>] {
+		return &self.population;
+	}
+
+	/// Non-dominated solutions found so far
+	pub fn archive(&self) -> &Archive<V> {
+		return &self.archive;
+	}
+}
+
+/// Keys ordering the molds by their front, and by crowding distance inside a front
+fn ranking_keys<V: Vector>(population: &[MultiSlime<V>]) -> Vec<f64> {
+	let mut result = vec![0.0; population.len()];
+	for (front_index, front) in pareto::non_dominated_sort(population).iter().enumerate() {
+		for (&index, distance) in front.iter().zip(pareto::crowding_distance(population, front)) {
+			result[index] = front_index as f64 + 0.5 / (1.0 + distance);
+		}
+	}
+	return result;
+}
+
+/// Keeps the given number of molds, taking whole fronts and the least crowded molds of the front that does not fit
+fn select<V: Vector>(combined: Vec<MultiSlime<V>>, count: usize) -> Vec<MultiSlime<V>> {
+	let mut chosen = Vec::with_capacity(count);
+	for front in pareto::non_dominated_sort(&combined) {
+		if chosen.len() + front.len() <= count {
+			chosen.extend(front);
+		} else {
+			let distances = pareto::crowding_distance(&combined, &front);
+			let mut order = (0..front.len()).collect::<Vec<_>>();
+			order.sort_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+			chosen.extend(order.into_iter().take(count - chosen.len()).map(|position| front[position]));
+		}
+		if chosen.len() == count {
+			break;
+		}
+	}
+	return chosen.into_iter().map(|index| combined[index].clone()).collect();
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{error::SlimeError, mosma::WorldState, multi_objective::MultiObjectiveProblem, pareto, random::RandomSource, vector::DynVector};
+
+	#[test]
+	fn converges_to_front_test() {
+		// the share of the hypervolume is lower for three objectives, as the archive is small for a surface
+		for (problem, dimensions, reference, limit, volume_share) in [
+			(MultiObjectiveProblem::Zdt1, 10, vec![1.1, 1.1], 0.05, 0.9),
+			(MultiObjectiveProblem::Zdt6, 10, vec![1.1, 1.1], 0.05, 0.9),
+			(MultiObjectiveProblem::Dtlz2, 12, vec![1.1, 1.1, 1.1], 0.25, 0.5),
+		] {
+			let mut world = WorldState::<DynVector>::new(50, Arc::new(problem), dimensions, 200, 0.03, 100, RandomSource::seed_from_u64(0)).unwrap();
+			let initial_front = world.archive().front();
+			let front = world.run().unwrap().iter().map(|solution| solution.values.clone()).collect::<Vec<_>>();
+			let true_front = problem.front(200);
+
+			let distance = pareto::inverted_generational_distance(&front, &true_front);
+			assert!(distance < limit, "{} ended {} away from the front", problem, distance);
+			assert!(distance < pareto::inverted_generational_distance(&initial_front, &true_front));
+			let volume = pareto::hypervolume(&front, &reference);
+			assert!(volume > volume_share * pareto::hypervolume(&true_front, &reference), "{} has hypervolume {}", problem, volume);
+			assert!(front.len() <= 100);
+			assert_eq!(world.evaluations(), 50 * 201);
+		}
+	}
+
+	#[test]
+	fn seeded_runs_are_reproducible_test() {
+		let run = |seed| {
+			let mut world = WorldState::<DynVector>::new(20, Arc::new(MultiObjectiveProblem::Zdt3), 5, 30, 0.03, 30, RandomSource::seed_from_u64(seed)).unwrap();
+			return world.run().unwrap().to_vec();
+		};
+		assert_eq!(run(3), run(3));
+		assert_ne!(run(3), run(4));
+
+		assert_eq!(WorldState::<DynVector>::new(20, Arc::new(MultiObjectiveProblem::Zdt1), 5, 30, 0.03, 0, RandomSource::seed_from_u64(0)).err(), Some(SlimeError::InvalidArchiveSize(0)));
+		assert!(matches!(WorldState::<DynVector>::new(20, Arc::new(MultiObjectiveProblem::Dtlz2), 2, 30, 0.03, 10, RandomSource::seed_from_u64(0)), Err(SlimeError::UnsupportedDimensions { .. })));
+	}
+}
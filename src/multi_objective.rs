@@ -0,0 +1,303 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{bounds::Bounds, pareto, vector::Vector};
+
+/// Function with several objectives optimized at once, every objective is minimized
+pub trait MultiObjective<V: Vector>: Send + Sync {
+	fn values(&self, input: &V) -> Vec<f64>;
+
+	fn objective_count(&self) -> usize;
+
+	/// Search space of the given dimension count
+	fn bounds(&self, dimensions: usize) -> Bounds<V>;
+
+	/// Name used when reporting results
+	fn name(&self) -> Option<&str> {
+		return None;
+	}
+
+	/// At most the given number of points spread over the true Pareto front, used as the reference of the indicators
+	fn pareto_front(&self, _points: usize) -> Option<Vec<Vec<f64>>> {
+		return None;
+	}
+}
+
+/// Number of objectives of the DTLZ problems
+pub const DTLZ_OBJECTIVES: usize = 3;
+
+/// ZDT problems with two objectives and DTLZ problems with `DTLZ_OBJECTIVES` objectives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiObjectiveProblem {
+	/// Convex front
+	Zdt1,
+	/// Concave front
+	Zdt2,
+	/// Front of five disconnected pieces
+	Zdt3,
+	/// Convex front with many local fronts
+	Zdt4,
+	/// Concave front with solutions unevenly distributed along it
+	Zdt6,
+	/// Linear front with many local fronts
+	Dtlz1,
+	/// Spherical front
+	Dtlz2,
+	/// Spherical front with many local fronts
+	Dtlz3,
+	/// Spherical front with solutions crowded near the edges
+	Dtlz4,
+	/// Front of four disconnected pieces
+	Dtlz7,
+}
+
+impl MultiObjectiveProblem {
+	pub const ALL: [MultiObjectiveProblem; 10] = [Self::Zdt1, Self::Zdt2, Self::Zdt3, Self::Zdt4, Self::Zdt6, Self::Dtlz1, Self::Dtlz2, Self::Dtlz3, Self::Dtlz4, Self::Dtlz7];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Zdt1 => return "zdt1",
+			Self::Zdt2 => return "zdt2",
+			Self::Zdt3 => return "zdt3",
+			Self::Zdt4 => return "zdt4",
+			Self::Zdt6 => return "zdt6",
+			Self::Dtlz1 => return "dtlz1",
+			Self::Dtlz2 => return "dtlz2",
+			Self::Dtlz3 => return "dtlz3",
+			Self::Dtlz4 => return "dtlz4",
+			Self::Dtlz7 => return "dtlz7",
+		}
+	}
+
+	/// Dimension count used in the papers introducing the problems
+	pub fn default_dimensions(self) -> usize {
+		match self {
+			Self::Zdt1 | Self::Zdt2 | Self::Zdt3 => return 30,
+			Self::Zdt4 | Self::Zdt6 => return 10,
+			Self::Dtlz1 => return DTLZ_OBJECTIVES + 4,
+			Self::Dtlz2 | Self::Dtlz3 | Self::Dtlz4 => return DTLZ_OBJECTIVES + 9,
+			Self::Dtlz7 => return DTLZ_OBJECTIVES + 19,
+		}
+	}
+
+	pub fn objective_count(self) -> usize {
+		match self {
+			Self::Zdt1 | Self::Zdt2 | Self::Zdt3 | Self::Zdt4 | Self::Zdt6 => return 2,
+			_ => return DTLZ_OBJECTIVES,
+		}
+	}
+
+	/// Takes at least as many coordinates as there are objectives
+	pub fn calculate(self, x: &[f64]) -> Vec<f64> {
+		let n = x.len() as f64;
+		match self {
+			Self::Zdt1 | Self::Zdt2 | Self::Zdt3 => {
+				let g = 1.0 + 9.0 * x[1..].iter().sum::<f64>() / (n - 1.0);
+				let ratio = x[0] / g;
+				let h = match self {
+					Self::Zdt1 => 1.0 - ratio.sqrt(),
+					Self::Zdt2 => 1.0 - ratio.powi(2),
+					_ => 1.0 - ratio.sqrt() - ratio * (10.0 * PI * x[0]).sin(),
+				};
+				return vec![x[0], g * h];
+			}
+			Self::Zdt4 => {
+				let g = 1.0 + 10.0 * (n - 1.0) + x[1..].iter().map(|a| a.powi(2) - 10.0 * (4.0 * PI * a).cos()).sum::<f64>();
+				return vec![x[0], g * (1.0 - (x[0] / g).sqrt())];
+			}
+			Self::Zdt6 => {
+				let first = 1.0 - (-4.0 * x[0]).exp() * (6.0 * PI * x[0]).sin().powi(6);
+				let g = 1.0 + 9.0 * (x[1..].iter().sum::<f64>() / (n - 1.0)).powf(0.25);
+				return vec![first, g * (1.0 - (first / g).powi(2))];
+			}
+			Self::Dtlz1 | Self::Dtlz2 | Self::Dtlz3 | Self::Dtlz4 | Self::Dtlz7 => {
+				let (position, distance) = x.split_at(DTLZ_OBJECTIVES - 1);
+				let k = distance.len() as f64;
+				let rastrigin_g = 100.0 * (k + distance.iter().map(|a| (a - 0.5).powi(2) - (20.0 * PI * (a - 0.5)).cos()).sum::<f64>());
+				let sphere_g = distance.iter().map(|a| (a - 0.5).powi(2)).sum::<f64>();
+				match self {
+					Self::Dtlz1 => return linear_front(position, 0.5 * (1.0 + rastrigin_g)),
+					Self::Dtlz2 => return spherical_front(position, 1.0 + sphere_g),
+					Self::Dtlz3 => return spherical_front(position, 1.0 + rastrigin_g),
+					Self::Dtlz4 => return spherical_front(&position.iter().map(|a| a.powi(100)).collect::<Vec<_>>(), 1.0 + sphere_g),
+					_ => {
+						let g = 1.0 + 9.0 * distance.iter().sum::<f64>() / k;
+						let h = DTLZ_OBJECTIVES as f64 - position.iter().map(|f| f / (1.0 + g) * (1.0 + (3.0 * PI * f).sin())).sum::<f64>();
+						let mut result = position.to_vec();
+						result.push((1.0 + g) * h);
+						return result;
+					}
+				}
+			}
+		}
+	}
+
+	/// At most the given number of points of the true Pareto front.
+	/// Disconnected fronts keep only the non-dominated part of a regular sample, so they get fewer points
+	pub fn front(self, points: usize) -> Vec<Vec<f64>> {
+		let points = points.max(2);
+		match self {
+			Self::Zdt1 | Self::Zdt2 | Self::Zdt3 | Self::Zdt4 | Self::Zdt6 => {
+				// the first objective of ZDT6 can not get below the minimum of 1 - exp(-4x) * sin(6 pi x)^6
+				let start = if self == Self::Zdt6 { 0.280_775_319_1 } else { 0.0 };
+				let front = (0..points).map(|index| {
+					let first = start + (1.0 - start) * index as f64 / (points - 1) as f64;
+					let second = match self {
+						Self::Zdt2 | Self::Zdt6 => 1.0 - first.powi(2),
+						Self::Zdt3 => 1.0 - first.sqrt() - first * (10.0 * PI * first).sin(),
+						_ => 1.0 - first.sqrt(),
+					};
+					return vec![first, second];
+				}).collect::<Vec<_>>();
+				return non_dominated(front);
+			}
+			Self::Dtlz1 => return simplex_lattice(points).into_iter().map(|weights| weights.iter().map(|w| w / 2.0).collect()).collect(),
+			Self::Dtlz2 | Self::Dtlz3 | Self::Dtlz4 => {
+				return simplex_lattice(points).into_iter().map(|weights| {
+					let norm = weights.iter().map(|w| w.powi(2)).sum::<f64>().sqrt();
+					return weights.iter().map(|w| w / norm).collect();
+				}).collect();
+			}
+			Self::Dtlz7 => {
+				// the front is the non-dominated part of the surface reached with g = 1
+				let side = (points as f64).sqrt().ceil() as usize;
+				let grid = (0..side * side).map(|index| {
+					let position = [index / side, index % side].map(|step| step as f64 / (side - 1) as f64);
+					let h = DTLZ_OBJECTIVES as f64 - position.iter().map(|f| f / 2.0 * (1.0 + (3.0 * PI * f).sin())).sum::<f64>();
+					return vec![position[0], position[1], 2.0 * h];
+				}).collect::<Vec<_>>();
+				return non_dominated(grid);
+			}
+		}
+	}
+}
+
+/// Objectives of DTLZ1 for the position coordinates, scaled by the distance term
+fn linear_front(position: &[f64], scale: f64) -> Vec<f64> {
+	let count = position.len() + 1;
+	return (0..count).map(|objective| {
+		let product = position[..count - 1 - objective].iter().product::<f64>();
+		let last = if objective > 0 { 1.0 - position[count - 1 - objective] } else { 1.0 };
+		return scale * product * last;
+	}).collect();
+}
+
+/// Objectives of DTLZ2 to DTLZ4 for the position coordinates, scaled by the distance term
+fn spherical_front(position: &[f64], scale: f64) -> Vec<f64> {
+	let count = position.len() + 1;
+	return (0..count).map(|objective| {
+		let product = position[..count - 1 - objective].iter().map(|a| (a * FRAC_PI_2).cos()).product::<f64>();
+		let last = if objective > 0 { (position[count - 1 - objective] * FRAC_PI_2).sin() } else { 1.0 };
+		return scale * product * last;
+	}).collect();
+}
+
+/// Evenly spread points with non-negative coordinates summing to one, as many as fit into the given count
+fn simplex_lattice(points: usize) -> Vec<Vec<f64>> {
+	let mut divisions = 1;
+	while (divisions + 2) * (divisions + 3) / 2 <= points {
+		divisions += 1;
+	}
+	let mut result = Vec::new();
+	for first in 0..=divisions {
+		for second in 0..=(divisions - first) {
+			let third = divisions - first - second;
+			result.push([first, second, third].iter().map(|&step| step as f64 / divisions as f64).collect());
+		}
+	}
+	return result;
+}
+
+fn non_dominated(points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+	let first_front = pareto::non_dominated_sort(&points).swap_remove(0);
+	return first_front.into_iter().map(|index| points[index].clone()).collect();
+}
+
+impl FromStr for MultiObjectiveProblem {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|&problem| problem.name() == name).ok_or_else(|| {
+			return format!("unknown multi-objective problem `{}`, available problems are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for MultiObjectiveProblem {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", MultiObjectiveProblem::name(*self));
+	}
+}
+
+impl<V: Vector> MultiObjective<V> for MultiObjectiveProblem {
+	fn values(&self, input: &V) -> Vec<f64> {
+		return self.calculate(input.as_slice());
+	}
+
+	fn objective_count(&self) -> usize {
+		return MultiObjectiveProblem::objective_count(*self);
+	}
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		let mut result = Bounds::<V>::uniform(dimensions, 0.0, 1.0);
+		if *self == Self::Zdt4 {
+			result.lower.as_mut_slice().iter_mut().skip(1).for_each(|a| *a = -5.0);
+			result.upper.as_mut_slice().iter_mut().skip(1).for_each(|a| *a = 5.0);
+		}
+		return result;
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some(MultiObjectiveProblem::name(*self));
+	}
+
+	fn pareto_front(&self, points: usize) -> Option<Vec<Vec<f64>>> {
+		return Some(self.front(points));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{multi_objective::{MultiObjectiveProblem, DTLZ_OBJECTIVES}, pareto};
+
+	#[test]
+	fn optimal_points_test() {
+		// coordinates past the first ones are at their optimal value, the first ones move along the front
+		for problem in MultiObjectiveProblem::ALL {
+			let dimensions = problem.default_dimensions();
+			let front = problem.front(200);
+			let optimal_distance = match problem {
+				MultiObjectiveProblem::Dtlz1 | MultiObjectiveProblem::Dtlz2 | MultiObjectiveProblem::Dtlz3 | MultiObjectiveProblem::Dtlz4 => 0.5,
+				_ => 0.0,
+			};
+			for step in 0..=10 {
+				let mut point = vec![optimal_distance; dimensions];
+				point[0] = step as f64 / 10.0;
+				if problem.objective_count() == DTLZ_OBJECTIVES {
+					point[1] = 1.0 - step as f64 / 10.0;
+				}
+				let values = problem.calculate(&point);
+				assert_eq!(values.len(), problem.objective_count());
+				// no point of the true front can be dominated
+				assert!(front.iter().all(|reference| values.iter().zip(reference).any(|(a, b)| *a >= b - 1e-9)), "{} at {:?} dominates the front", problem, point);
+				// disconnected fronts are only a part of the surface the points lie on
+				if ![MultiObjectiveProblem::Zdt3, MultiObjectiveProblem::Dtlz7].contains(&problem) {
+					let nearest = pareto::inverted_generational_distance(&front, &[&values]);
+					assert!(nearest < 0.1, "{} at {:?} is {:?}, {} away from the front", problem, point, values, nearest);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn front_size_test() {
+		for problem in MultiObjectiveProblem::ALL {
+			let front = problem.front(100);
+			assert!((10..=100).contains(&front.len()), "{} has {} points", problem, front.len());
+			assert!(front.iter().all(|point| point.len() == problem.objective_count()));
+		}
+		assert_eq!(MultiObjectiveProblem::Zdt1.front(100).len(), 100);
+		assert_eq!(MultiObjectiveProblem::Dtlz2.front(100).len(), 91);
+	}
+}
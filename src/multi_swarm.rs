@@ -0,0 +1,217 @@
+use rand::prelude::*;
+
+use crate::{constraints::{Evaluation, Ranking}, error::SlimeError, fitness::Direction, observer::MigrationEvent, optimizer::{Candidate, Optimizer, PopulationOptimizer}, random::RandomSource, vector::Vector};
+
+/// Island model running several independent swarms of any population based algorithm, migrating members between them
+#[derive(Clone, Debug)]
+pub struct MultiSwarm<V: Vector, O: PopulationOptimizer<V>> {
+	swarms: Vec<O>,
+	current_iteration: usize,
+	migration_threshold: f64,
+	random_source: RandomSource,
+	last_migrations: Vec<MigrationEvent>,
+	pub best_solution: V,
+	pub best_solution_value: f64,
+	best_violation: f64,
+}
+
+impl<V: Vector, O: PopulationOptimizer<V>> MultiSwarm<V, O> {
+	/// Every swarm receives its own generator, derived from the one given to the multi-swarm
+	pub fn new(swarm_count: usize, migration_threshold: f64, mut rng_source: RandomSource, mut make_swarm: impl FnMut(RandomSource) -> Result<O, SlimeError>) -> Result<Self, SlimeError> {
+		if swarm_count == 0 {
+			return Err(SlimeError::InvalidSwarmCount(swarm_count));
+		}
+		if migration_threshold.is_nan() || migration_threshold < 0.0 {
+			return Err(SlimeError::InvalidMigrationThreshold(migration_threshold));
+		}
+		let swarms = (0..swarm_count).map(|_| make_swarm(RandomSource::from_rng(&mut rng_source).unwrap())).collect::<Result<Vec<_>, _>>()?;
+		let mut result = Self {
+			best_solution: swarms[0].best_solution(),
+			best_solution_value: swarms[0].best_value(),
+			best_violation: swarms[0].best_violation(),
+			random_source: rng_source,
+			swarms, migration_threshold,
+			current_iteration: 0,
+			last_migrations: Vec::new(),
+		};
+		result.update_best_solutions();
+
+		return Ok(result);
+	}
+
+	pub fn swarms(&self) -> &[O] {
+		return &self.swarms;
+	}
+
+	/// Takes the best of the swarm bests. Swarms never lose their best solution, but its value can change when it is re-evaluated
+	fn update_best_solutions(&mut self) {
+		let ranking = self.ranking();
+		self.best_solution = self.swarms[0].best_solution();
+		self.best_solution_value = self.swarms[0].best_value();
+		self.best_violation = self.swarms[0].best_violation();
+		for swarm in self.swarms.iter() {
+			if ranking.is_better(Evaluation::new(swarm.best_value(), swarm.best_violation()), Evaluation::new(self.best_solution_value, self.best_violation)) {
+				self.best_solution = swarm.best_solution();
+				self.best_solution_value = swarm.best_value();
+				self.best_violation = swarm.best_violation();
+			}
+		}
+	}
+
+	fn migrate(&mut self) {
+		self.last_migrations.clear();
+		let size = self.swarms.len();
+		let direction = self.direction();
+		let ranking = self.ranking();
+		let evaluation = |candidate: &O::Individual| Evaluation::new(candidate.fitness(), candidate.violation());
+		// iterate over distinct pairs
+		// Can't use iterators as it requires mutable references to two swarm
+		// Will migrate best from both parts to one of the swarms and worst to the other
+		for first_index in 0..(size - 1) {
+			for second_index in (first_index + 1)..size {
+				let first_best = self.swarms[first_index].best_value();
+				let second_best = self.swarms[second_index].best_value();
+				let difference = (second_best - first_best).abs();
+				if difference > self.migration_threshold {
+					// relative to the worse of both values, computed on the equivalent minimization problem
+					let lambda = difference / direction.signed(second_best).max(direction.signed(first_best));
+					// lambda exceeds one when the best values have different signs
+					let smaller_size = self.swarms[first_index].population().len().min(self.swarms[second_index].population().len());
+					let migration_count = ((lambda * self.swarms[first_index].population().len() as f64) as usize).min(smaller_size);
+
+					let mut sorted_first = self.swarms[first_index].population().to_vec();
+					sorted_first.sort_unstable_by(|a, b| ranking.compare(evaluation(a), evaluation(b))); // Worst are first
+
+					let mut sorted_second_reverse = self.swarms[second_index].population().to_vec();
+					sorted_second_reverse.sort_unstable_by(|a, b| ranking.compare(evaluation(a), evaluation(b)).reverse()); // Best are first
+
+					let mut migrated_first = sorted_first.split_off(migration_count); // contains best from first
+					let mut left_second = sorted_second_reverse.split_off(migration_count); // contains worst from second
+
+					// sorted_first now contains the unmigrated part of first swarm, so does left_second for the second swarm
+					// now merge the migrated and unmigrated parts
+
+					migrated_first.append(&mut sorted_second_reverse);
+					sorted_first.append(&mut left_second);
+
+					migrated_first.shuffle(&mut self.random_source);
+					sorted_first.shuffle(&mut self.random_source);
+
+					// replacing also updates both best solutions
+					self.swarms[first_index].replace_population(migrated_first);
+					self.swarms[second_index].replace_population(sorted_first);
+
+					self.last_migrations.push(MigrationEvent {
+						iteration: self.current_iteration + 1,
+						first_swarm: first_index,
+						second_swarm: second_index,
+						migrated_count: migration_count,
+					});
+				}
+			}
+		}
+	}
+}
+
+impl<V: Vector, O: PopulationOptimizer<V>> Optimizer<V> for MultiSwarm<V, O> {
+	fn step(&mut self) -> Result<(), SlimeError> {
+		for swarm in &mut self.swarms {
+			swarm.step()?;
+		}
+		self.migrate();
+		self.update_best_solutions();
+		self.current_iteration += 1;
+		return Ok(());
+	}
+
+	fn current_iteration(&self) -> usize {
+		return self.current_iteration;
+	}
+
+	fn iteration_count(&self) -> usize {
+		return self.swarms.iter().map(O::iteration_count).max().unwrap_or(0);
+	}
+
+	fn reset(&mut self) -> Result<(), SlimeError> {
+		self.best_solution_value = self.direction().worst();
+		for swarm in &mut self.swarms {
+			swarm.reset()?;
+		}
+		self.current_iteration = 0;
+		self.last_migrations.clear();
+		self.update_best_solutions();
+		return Ok(());
+	}
+
+	fn direction(&self) -> Direction {
+		return self.swarms[0].direction();
+	}
+
+	fn best_solution(&self) -> V {
+		return self.best_solution.clone();
+	}
+
+	fn best_value(&self) -> f64 {
+		return self.best_solution_value;
+	}
+
+	fn best_violation(&self) -> f64 {
+		return self.best_violation;
+	}
+
+	/// Ranking of the first swarm, the adaptive parameters of the constraint handling differ slightly between swarms
+	fn ranking(&self) -> Ranking {
+		return self.swarms[0].ranking();
+	}
+
+	fn evaluations(&self) -> usize {
+		return self.swarms.iter().map(O::evaluations).sum();
+	}
+
+	fn last_migrations(&self) -> &[MigrationEvent] {
+		return &self.last_migrations;
+	}
+
+	fn candidates(&self) -> Vec<&dyn Candidate<V>> {
+		return self.swarms.iter().flat_map(O::candidates).collect();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, multi_swarm::MultiSwarm, optimizer::{Optimizer, PopulationOptimizer}, random::RandomSource, slime, vector::VectorN};
+
+	#[test]
+	fn migration_keeps_swarm_sizes() {
+		let function = Arc::new(Functions::Rastrigin);
+		let mut world = MultiSwarm::<VectorN<3>, _>::new(4, 0.0, RandomSource::seed_from_u64(0), |rng_source| {
+			return slime::WorldState::new(10, function.clone(), 3, 20, 0.03, FitnessSettings::new(), rng_source);
+		}).unwrap();
+		world.run().unwrap();
+
+		assert_eq!(world.current_iteration(), 20);
+		for swarm in world.swarms() {
+			assert_eq!(swarm.population().len(), 10);
+			assert!(world.best_value() <= swarm.best_value());
+		}
+	}
+
+	#[test]
+	fn seeded_runs_are_reproducible() {
+		let function = Arc::new(Functions::Ackley);
+		let run = |seed| {
+			let mut world = MultiSwarm::<VectorN<3>, _>::new(3, 0.1, RandomSource::seed_from_u64(seed), |rng_source| {
+				return slime::WorldState::new(10, function.clone(), 3, 30, 0.03, FitnessSettings::new(), rng_source);
+			}).unwrap();
+			world.run().unwrap();
+			return (world.best_solution(), world.best_value());
+		};
+
+		assert_eq!(run(7), run(7));
+		assert_ne!(run(7), run(8));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::{bounds::Bounds, error::SlimeError, objective::{Objective, Optimum}, random::RandomSource, vector::Vector};
+
+/// How the Gaussian noise is combined with the objective value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseModel {
+	/// `f(x) + sigma * N(0, 1)`
+	#[default]
+	Additive,
+	/// `f(x) * (1 + sigma * N(0, 1))`, keeping the noise proportional to the value
+	Multiplicative,
+}
+
+impl NoiseModel {
+	pub const ALL: [NoiseModel; 2] = [Self::Additive, Self::Multiplicative];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Additive => return "additive",
+			Self::Multiplicative => return "multiplicative",
+		}
+	}
+
+	pub fn apply(self, value: f64, noise: f64) -> f64 {
+		match self {
+			Self::Additive => return value + noise,
+			Self::Multiplicative => return value * (1.0 + noise),
+		}
+	}
+}
+
+impl FromStr for NoiseModel {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		return Self::ALL.into_iter().find(|model| model.name() == name).ok_or_else(|| {
+			return format!("unknown noise model `{}`, available models are: {}", name, Self::ALL.map(Self::name).join(", "));
+		});
+	}
+}
+
+impl Display for NoiseModel {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		return write!(f, "{}", self.name());
+	}
+}
+
+/// Objective returning a different noisy value on every evaluation, simulating stochastic simulations.
+/// The generator is shared by every evaluation, so a noisy objective should not be shared between runs that have to be reproducible
+pub struct Noisy<V: Vector> {
+	inner: Arc<dyn Objective<V>>,
+	model: NoiseModel,
+	sigma: f64,
+	random_source: Mutex<RandomSource>,
+	name: Option<String>,
+}
+
+impl<V: Vector> Noisy<V> {
+	pub fn new(inner: Arc<dyn Objective<V>>, model: NoiseModel, sigma: f64, random_source: RandomSource) -> Result<Self, SlimeError> {
+		if !(sigma.is_finite() && sigma >= 0.0) {
+			return Err(SlimeError::InvalidTransform(format!("noise sigma must be a non-negative number, got {}", sigma)));
+		}
+		return Ok(Self {
+			name: inner.name().map(|name| format!("noisy-{}", name)),
+			random_source: Mutex::new(random_source),
+			inner, model, sigma,
+		});
+	}
+}
+
+impl<V: Vector> Objective<V> for Noisy<V> {
+	fn value(&self, input: &V) -> f64 {
+		let noise = self.sigma * self.random_source.lock().unwrap().sample::<f64, _>(StandardNormal);
+		return self.model.apply(self.inner.value(input), noise);
+	}
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		return self.inner.bounds(dimensions);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.name.as_deref();
+	}
+
+	/// Optimum of the noise-free objective, which is also the minimum of the expected value
+	fn optimum(&self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum(dimensions);
+	}
+
+	/// Constraints are not affected by the noise
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.inequality_constraints(input);
+	}
+
+	fn equality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.equality_constraints(input);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, noise::{NoiseModel, Noisy}, objective::Objective, optimizer::Optimizer, particles, random::RandomSource, slime, vector::VectorN};
+
+	#[test]
+	fn noise_statistics_test() {
+		let noisy = Noisy::new(Arc::new(Functions::Sphere), NoiseModel::Additive, 2.0, RandomSource::seed_from_u64(0)).unwrap();
+		let point = VectorN::new([1.0, 2.0]);
+		let values = (0..10000).map(|_| noisy.value(&point)).collect::<Vec<_>>();
+		let mean = values.iter().sum::<f64>() / values.len() as f64;
+		let deviation = (values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+		assert!((mean - 5.0).abs() < 0.1, "mean is {}", mean);
+		assert!((deviation - 2.0).abs() < 0.1, "deviation is {}", deviation);
+
+		let multiplicative = Noisy::<VectorN<2>>::new(Arc::new(Functions::Sphere), NoiseModel::Multiplicative, 2.0, RandomSource::seed_from_u64(0)).unwrap();
+		assert_eq!(multiplicative.value(&VectorN::default()), 0.0);
+		assert!(Noisy::<VectorN<2>>::new(Arc::new(Functions::Sphere), NoiseModel::Additive, -1.0, RandomSource::seed_from_u64(0)).is_err());
+	}
+
+	#[test]
+	fn reevaluation_test() {
+		// the best value of a noisy run is biased downwards, averaging evaluations brings it closer to the true value
+		let run = |reevaluations: usize, particle_swarm: bool| {
+			let noisy = Arc::new(Noisy::new(Arc::new(Functions::Sphere), NoiseModel::Additive, 1.0, RandomSource::seed_from_u64(1)).unwrap());
+			let settings = FitnessSettings::new().with_reevaluations(reevaluations);
+			let mut world: Box<dyn Optimizer<VectorN<3>>> = if particle_swarm {
+				let coefficients = particles::Coefficients { social: 0.6, cognitive: 0.5, inertia: 0.7 };
+				Box::new(particles::WorldState::new(20, noisy, 3, 100, coefficients, settings, RandomSource::seed_from_u64(0)).unwrap())
+			} else {
+				Box::new(slime::WorldState::new(20, noisy, 3, 100, 0.03, settings, RandomSource::seed_from_u64(0)).unwrap())
+			};
+			let result = world.run().unwrap();
+			let true_value = Functions::Sphere.value(&result.best_solution);
+			return (result.best_value - true_value, result.evaluations);
+		};
+		for particle_swarm in [false, true] {
+			let (single_bias, single_evaluations) = run(0, particle_swarm);
+			let (averaged_bias, averaged_evaluations) = run(20, particle_swarm);
+			assert!(single_bias < -1.0, "bias of a single evaluation is {}", single_bias);
+			assert!(averaged_bias.abs() < single_bias.abs(), "bias {} is not smaller than {}", averaged_bias, single_bias);
+			assert!(averaged_evaluations > single_evaluations);
+		}
+	}
+}
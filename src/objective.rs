@@ -0,0 +1,256 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{bounds::Bounds, constraints::{self, ConstraintState, Evaluation, Ranking}, error::SlimeError, fitness::{FitnessSettings, NonFinitePolicy, MAX_RESAMPLES}, vector::Vector};
+
+/// Function optimized by the optimizers, minimized unless the fitness settings say otherwise
+pub trait Objective<V: Vector>: Send + Sync {
+	fn value(&self, input: &V) -> f64;
+
+	/// Search space of the given dimension count. Problems with a fixed size can ignore the argument
+	fn bounds(&self, dimensions: usize) -> Bounds<V>;
+
+	/// Name used when reporting results
+	fn name(&self) -> Option<&str> {
+		return None;
+	}
+
+	/// Known global minimum of the given dimension count, used to measure the error of found solutions
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		return None;
+	}
+
+	/// Values of the inequality constraints `g(x) <= 0`, empty for problems limited only by the bounds
+	fn inequality_constraints(&self, _input: &V) -> Vec<f64> {
+		return Vec::new();
+	}
+
+	/// Values of the equality constraints `h(x) = 0`
+	fn equality_constraints(&self, _input: &V) -> Vec<f64> {
+		return Vec::new();
+	}
+}
+
+/// Constraint functions of `FnObjective`, returning the value of every constraint at the point
+pub type ConstraintFn<V> = Arc<dyn Fn(&V) -> Vec<f64> + Send + Sync>;
+
+/// Global minimum of an objective
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimum<V: Vector> {
+	pub value: f64,
+	/// Point at which the minimum is reached, `None` if there are several of them
+	pub location: Option<V>,
+}
+
+impl<V: Vector> Optimum<V> {
+	/// Difference between the value and the optimum, `f(x) - f*`
+	pub fn error(&self, value: f64) -> f64 {
+		return value - self.value;
+	}
+
+	/// Euclidean distance between the point and the optimum location
+	pub fn distance(&self, point: &V) -> Option<f64> {
+		return self.location.as_ref().map(|location| {
+			return (point.clone() - location.clone()).as_slice().iter().map(|a| a.powi(2)).sum::<f64>().sqrt();
+		});
+	}
+}
+
+/// Objective defined by a closure, for problems that are not part of the crate
+#[derive(Clone)]
+pub struct FnObjective<V: Vector, F> {
+	function: F,
+	bounds: Bounds<V>,
+	name: Option<String>,
+	optimum: Option<Optimum<V>>,
+	inequalities: Option<ConstraintFn<V>>,
+	equalities: Option<ConstraintFn<V>>,
+}
+
+impl<V: Vector, F> FnObjective<V, F> {
+	pub fn new(function: F, bounds: Bounds<V>) -> Self {
+		return Self {
+			function, bounds,
+			name: None,
+			optimum: None,
+			inequalities: None,
+			equalities: None,
+		};
+	}
+
+	pub fn with_name(mut self, name: impl Into<String>) -> Self {
+		self.name = Some(name.into());
+		return self;
+	}
+
+	pub fn with_optimum(mut self, optimum: Optimum<V>) -> Self {
+		self.optimum = Some(optimum);
+		return self;
+	}
+
+	pub fn with_inequality_constraints(mut self, constraints: impl Fn(&V) -> Vec<f64> + Send + Sync + 'static) -> Self {
+		self.inequalities = Some(Arc::new(constraints));
+		return self;
+	}
+
+	pub fn with_equality_constraints(mut self, constraints: impl Fn(&V) -> Vec<f64> + Send + Sync + 'static) -> Self {
+		self.equalities = Some(Arc::new(constraints));
+		return self;
+	}
+}
+
+impl<V: Vector, F: Fn(&V) -> f64 + Send + Sync> Objective<V> for FnObjective<V, F> {
+	fn value(&self, input: &V) -> f64 {
+		return (self.function)(input);
+	}
+
+	fn bounds(&self, _dimensions: usize) -> Bounds<V> {
+		return self.bounds.clone();
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.name.as_deref();
+	}
+
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		return self.optimum.clone();
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inequalities.as_ref().map_or_else(Vec::new, |constraints| constraints(input));
+	}
+
+	fn equality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.equalities.as_ref().map_or_else(Vec::new, |constraints| constraints(input));
+	}
+}
+
+/// Wrapper around an objective counting how many times it was evaluated
+#[derive(Clone)]
+pub struct EvaluationCounter<V: Vector> {
+	objective: Arc<dyn Objective<V>>,
+	count: usize,
+	settings: FitnessSettings,
+	constraints: ConstraintState,
+}
+
+impl<V: Vector> EvaluationCounter<V> {
+	pub fn new(objective: Arc<dyn Objective<V>>) -> Self {
+		return Self {
+			objective,
+			count: 0,
+			settings: FitnessSettings::default(),
+			constraints: ConstraintState::new(&FitnessSettings::default()),
+		};
+	}
+
+	pub fn with_settings(mut self, settings: FitnessSettings) -> Self {
+		self.settings = settings;
+		self.constraints = ConstraintState::new(&settings);
+		return self;
+	}
+
+	pub fn settings(&self) -> FitnessSettings {
+		return self.settings;
+	}
+
+	pub fn evaluate(&mut self, input: &V) -> f64 {
+		self.count += 1;
+		return self.objective.value(input);
+	}
+
+	/// Evaluates the objective, handling NaN and infinite values according to the policy.
+	/// Resampling moves the point and counts every attempt as an evaluation
+	pub fn evaluate_checked(&mut self, point: &mut V, bounds: &Bounds<V>, random_source: &mut impl Rng) -> Result<f64, SlimeError> {
+		let value = self.evaluate(point);
+		if value.is_finite() {
+			return Ok(value);
+		}
+		match self.settings.non_finite_policy {
+			NonFinitePolicy::Worst => return Ok(self.settings.direction.worst_finite()),
+			NonFinitePolicy::Abort => return Err(SlimeError::NonFiniteFitness),
+			NonFinitePolicy::Resample => {
+				for _ in 0..MAX_RESAMPLES {
+					*point = bounds.sample(random_source);
+					let value = self.evaluate(point);
+					if value.is_finite() {
+						return Ok(value);
+					}
+				}
+				return Err(SlimeError::NonFiniteFitness);
+			}
+		}
+	}
+
+	/// Same as `evaluate_checked`, also computing the total constraint violation at the final position of the point
+	pub fn evaluate_constrained(&mut self, point: &mut V, bounds: &Bounds<V>, random_source: &mut impl Rng) -> Result<Evaluation, SlimeError> {
+		let value = self.evaluate_checked(point, bounds, random_source)?;
+		let violation = constraints::violation(&self.objective.inequality_constraints(point), &self.objective.equality_constraints(point), self.settings.equality_tolerance);
+		return Ok(Evaluation::new(value, violation));
+	}
+
+	/// Current order of evaluations, following the direction and the constraint handling
+	pub fn ranking(&self) -> Ranking {
+		return self.constraints.ranking();
+	}
+
+	pub fn constraint_state(&mut self) -> &mut ConstraintState {
+		return &mut self.constraints;
+	}
+
+	/// Averages `reevaluations` more evaluations of the point into a mean of `samples` earlier ones, skipping non-finite values.
+	/// Returns the new mean and sample count, which are unchanged if re-evaluation is disabled
+	pub fn refine(&mut self, point: &V, mean: f64, samples: usize) -> (f64, usize) {
+		if samples == 0 || !mean.is_finite() {
+			return (mean, samples);
+		}
+		let mut sum = mean * samples as f64;
+		let mut samples = samples;
+		for _ in 0..self.settings.reevaluations {
+			let value = self.evaluate(point);
+			if value.is_finite() {
+				sum += value;
+				samples += 1;
+			}
+		}
+		return (sum / samples as f64, samples);
+	}
+
+	pub fn count(&self) -> usize {
+		return self.count;
+	}
+
+	pub fn reset_count(&mut self) {
+		self.count = 0;
+	}
+
+	pub fn objective(&self) -> &dyn Objective<V> {
+		return self.objective.as_ref();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{bounds::Bounds, fitness::FitnessSettings, objective::{FnObjective, Objective, Optimum}, optimizer::Optimizer, random::RandomSource, slime, vector::{QuickFold, VectorN}};
+
+	#[test]
+	fn closure_objective_test() {
+		let sphere = FnObjective::new(|input: &VectorN<2>| input.coordinates.map(|a| a.powi(2)).sum(), Bounds::uniform(2, -5.0, 5.0))
+			.with_name("sphere")
+			.with_optimum(Optimum { value: 0.0, location: Some(VectorN::default()) });
+		assert_eq!(sphere.value(&VectorN::new([1.0, 2.0])), 5.0);
+		assert_eq!(sphere.name(), Some("sphere"));
+		let optimum = sphere.optimum(2).unwrap();
+		assert_eq!(optimum.error(5.0), 5.0);
+		assert_eq!(optimum.distance(&VectorN::new([3.0, 4.0])), Some(5.0));
+
+		let mut world = slime::WorldState::new(20, Arc::new(sphere), 2, 100, 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap();
+		world.run().unwrap();
+		assert!(world.best_value() < 1.0);
+	}
+}
@@ -0,0 +1,102 @@
+use crate::{optimizer::Candidate, vector::Vector};
+
+/// Read-only view of an optimizer after an iteration
+pub struct IterationView<'a, V: Vector> {
+	pub iteration: usize,
+	pub population: &'a [&'a dyn Candidate<V>],
+	pub best_solution: &'a V,
+	pub best_value: f64,
+	pub evaluations: usize,
+}
+
+/// Exchange of population members between two swarms of a multi-swarm optimizer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationEvent {
+	pub iteration: usize,
+	pub first_swarm: usize,
+	pub second_swarm: usize,
+	/// Number of members sent from each swarm to the other
+	pub migrated_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverAction {
+	Continue,
+	Abort,
+}
+
+/// Hooks called while an optimizer runs, e.g. for logging, live plots or aborting runs early
+pub trait Observer<V: Vector> {
+	fn on_iteration(&mut self, view: &IterationView<V>) -> ObserverAction;
+
+	fn on_migration(&mut self, _event: &MigrationEvent) {}
+}
+
+/// Observer doing nothing, used by runs without hooks
+impl<V: Vector> Observer<V> for () {
+	fn on_iteration(&mut self, _view: &IterationView<V>) -> ObserverAction {
+		return ObserverAction::Continue;
+	}
+}
+
+impl<V: Vector, F: FnMut(&IterationView<V>) -> ObserverAction> Observer<V> for F {
+	fn on_iteration(&mut self, view: &IterationView<V>) -> ObserverAction {
+		return self(view);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, multi_swarm::MultiSwarm, observer::{IterationView, MigrationEvent, Observer, ObserverAction}, optimizer::Optimizer, random::RandomSource, slime, stopping::{StoppingCriteria, TerminationReason}, vector::VectorN};
+
+	struct CountingObserver {
+		iterations: Vec<usize>,
+		migrations: usize,
+	}
+
+	impl Observer<VectorN<3>> for CountingObserver {
+		fn on_iteration(&mut self, view: &IterationView<VectorN<3>>) -> ObserverAction {
+			assert_eq!(view.population.len(), 30);
+			self.iterations.push(view.iteration);
+			return ObserverAction::Continue;
+		}
+
+		fn on_migration(&mut self, event: &MigrationEvent) {
+			assert!(event.first_swarm < event.second_swarm);
+			self.migrations += 1;
+		}
+	}
+
+	fn make_world() -> MultiSwarm<VectorN<3>, slime::WorldState<VectorN<3>>> {
+		return MultiSwarm::new(3, 0.0, RandomSource::seed_from_u64(0), |rng_source| {
+			return slime::WorldState::new(10, Arc::new(Functions::Schwefel2), 3, 20, 0.03, FitnessSettings::new(), rng_source);
+		}).unwrap();
+	}
+
+	#[test]
+	fn observer_calls_test() {
+		let mut observer = CountingObserver { iterations: Vec::new(), migrations: 0 };
+		make_world().run_observed(&StoppingCriteria::new(), &mut observer).unwrap();
+
+		assert_eq!(observer.iterations, (1..=20).collect::<Vec<_>>());
+		assert!(observer.migrations > 0);
+	}
+
+	#[test]
+	fn observer_abort_test() {
+		let mut abort_after_five = |view: &IterationView<VectorN<3>>| {
+			if view.iteration == 5 {
+				return ObserverAction::Abort;
+			}
+			return ObserverAction::Continue;
+		};
+		let result = make_world().run_observed(&StoppingCriteria::new(), &mut abort_after_five).unwrap();
+
+		assert_eq!(result.termination_reason, TerminationReason::ObserverAbort);
+		assert_eq!(result.iterations, 5);
+	}
+}
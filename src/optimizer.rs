@@ -0,0 +1,174 @@
+use crate::{constraints::{Evaluation, Ranking}, error::SlimeError, fitness::Direction, history::IterationRecord, observer::{IterationView, MigrationEvent, Observer, ObserverAction}, stopping::{StoppingCriteria, StoppingState, TerminationReason}, vector::Vector};
+
+/// Outcome of a single run of an optimizer
+#[derive(Debug, Clone)]
+pub struct RunResult<V: Vector> {
+	pub best_solution: V,
+	pub best_value: f64,
+	/// Total constraint violation of the best solution, zero if it is feasible
+	pub best_violation: f64,
+	pub iterations: usize,
+	pub evaluations: usize,
+	pub termination_reason: TerminationReason,
+	/// Records of the initial population and of every performed iteration
+	pub history: Vec<IterationRecord>,
+}
+
+/// Interface shared by every optimization algorithm, so they can be driven without knowing which one is running
+pub trait Optimizer<V: Vector> {
+	/// Performs a single iteration of the algorithm
+	fn step(&mut self) -> Result<(), SlimeError>;
+
+	/// Number of iterations performed since construction or the last reset
+	fn current_iteration(&self) -> usize;
+
+	/// Number of iterations performed by `run`
+	fn iteration_count(&self) -> usize;
+
+	/// Reinitializes the population, leaving the optimizer as if it was freshly constructed
+	fn reset(&mut self) -> Result<(), SlimeError>;
+
+	/// Whether the optimizer looks for the lowest or the highest value
+	fn direction(&self) -> Direction;
+
+	fn best_solution(&self) -> V;
+
+	fn best_value(&self) -> f64;
+
+	/// Total constraint violation of the best solution
+	fn best_violation(&self) -> f64 {
+		return 0.0;
+	}
+
+	/// Order of evaluations used by the optimizer, combining the direction with the constraint handling
+	fn ranking(&self) -> Ranking {
+		return Ranking::unconstrained(self.direction());
+	}
+
+	/// Number of objective function evaluations since construction or the last reset
+	fn evaluations(&self) -> usize;
+
+	/// Current members of the population
+	fn candidates(&self) -> Vec<&dyn Candidate<V>>;
+
+	/// Migrations performed during the last iteration, only multi-swarm optimizers migrate
+	fn last_migrations(&self) -> &[MigrationEvent] {
+		return &[];
+	}
+
+	/// Performs iterations until the iteration count is reached or any of the criteria is met
+	fn run_until(&mut self, criteria: &StoppingCriteria) -> Result<RunResult<V>, SlimeError> {
+		return self.run_observed(criteria, &mut ());
+	}
+
+	/// Same as `run_until`, calling the observer after every iteration and migration
+	fn run_observed(&mut self, criteria: &StoppingCriteria, observer: &mut dyn Observer<V>) -> Result<RunResult<V>, SlimeError> {
+		let mut state = StoppingState::new(Evaluation::new(self.best_value(), self.best_violation()));
+		let mut history = vec![IterationRecord::from_optimizer(self)];
+		let termination_reason = loop {
+			if self.current_iteration() >= self.iteration_count() {
+				break TerminationReason::IterationLimit;
+			}
+			self.step()?;
+			history.push(IterationRecord::from_optimizer(self));
+			for event in self.last_migrations() {
+				observer.on_migration(event);
+			}
+			let view = IterationView {
+				iteration: self.current_iteration(),
+				population: &self.candidates(),
+				best_solution: &self.best_solution(),
+				best_value: self.best_value(),
+				evaluations: self.evaluations(),
+			};
+			if observer.on_iteration(&view) == ObserverAction::Abort {
+				break TerminationReason::ObserverAbort;
+			}
+			let best = Evaluation::new(self.best_value(), self.best_violation());
+			if let Some(reason) = state.check(criteria, best, self.ranking(), self.evaluations(), &self.candidates()) {
+				break reason;
+			}
+		};
+
+		return Ok(RunResult {
+			best_solution: self.best_solution(),
+			best_value: self.best_value(),
+			best_violation: self.best_violation(),
+			iterations: self.current_iteration(),
+			evaluations: self.evaluations(),
+			termination_reason,
+			history,
+		});
+	}
+
+	/// Performs all remaining iterations
+	fn run(&mut self) -> Result<RunResult<V>, SlimeError> {
+		return self.run_until(&StoppingCriteria::default());
+	}
+}
+
+/// Single member of a population, e.g. a slime mold or a particle
+pub trait Candidate<V: Vector> {
+	fn position(&self) -> &V;
+
+	fn fitness(&self) -> f64;
+
+	/// Total constraint violation at the position
+	fn violation(&self) -> f64 {
+		return 0.0;
+	}
+}
+
+/// Optimizer working on a population which can be exchanged with other instances of the same algorithm
+pub trait PopulationOptimizer<V: Vector>: Optimizer<V> {
+	type Individual: Candidate<V> + Clone;
+
+	fn population(&self) -> &[Self::Individual];
+
+	/// Replaces the whole population, updating the best solution with the new members
+	fn replace_population(&mut self, population: Vec<Self::Individual>);
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, optimizer::Optimizer, particles, random::RandomSource, slime, vector::VectorN};
+
+	#[test]
+	fn step_run_reset_test() {
+		let coefficients = particles::Coefficients { social: 1.5, cognitive: 1.5, inertia: 0.7 };
+		let worlds: [Box<dyn Optimizer<VectorN<3>>>; 2] = [
+			Box::new(slime::WorldState::new(20, Arc::new(Functions::Rastrigin), 3, 50, 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap()),
+			Box::new(particles::WorldState::new(20, Arc::new(Functions::Rastrigin), 3, 50, coefficients, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap()),
+		];
+		for mut world in worlds {
+			let initial_evaluations = world.evaluations();
+			assert_eq!(world.current_iteration(), 0);
+			assert_eq!(world.candidates().len(), 20);
+
+			let mut best_value = world.best_value();
+			for iteration in 1..=10 {
+				world.step().unwrap();
+				assert_eq!(world.current_iteration(), iteration);
+				assert!(world.best_value() <= best_value);
+				best_value = world.best_value();
+			}
+			let result = world.run().unwrap();
+			assert_eq!(world.current_iteration(), world.iteration_count());
+			assert!(result.best_value <= best_value);
+			assert_eq!(result.best_value, world.best_value());
+			assert_eq!(result.iterations, world.iteration_count());
+			assert!(world.evaluations() > initial_evaluations);
+
+			world.reset().unwrap();
+			assert_eq!(world.current_iteration(), 0);
+			assert_eq!(world.evaluations(), initial_evaluations);
+			assert_eq!(world.candidates().len(), 20);
+			assert!(world.candidates().iter().any(|candidate| candidate.fitness() == world.best_value()));
+			assert_eq!(world.run().unwrap().iterations, world.iteration_count());
+		}
+	}
+}
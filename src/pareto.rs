@@ -0,0 +1,219 @@
+use crate::{fitness, vector::Vector};
+
+/// Whether the first objective vector is at least as good in every objective and better in at least one, all objectives are minimized
+pub fn dominates(first: &[f64], second: &[f64]) -> bool {
+	let mut better = false;
+	for (a, b) in first.iter().zip(second) {
+		if a > b {
+			return false;
+		}
+		better |= a < b;
+	}
+	return better;
+}
+
+/// Splits the points into fronts of mutually non-dominated points, the first front is not dominated by any point.
+/// Returns indices of the points
+pub fn non_dominated_sort<P: AsRef<[f64]>>(points: &[P]) -> Vec<Vec<usize>> {
+	let mut dominated_by = vec![Vec::new(); points.len()];
+	let mut domination_count = vec![0usize; points.len()];
+	for first in 0..points.len() {
+		for second in (first + 1)..points.len() {
+			if dominates(points[first].as_ref(), points[second].as_ref()) {
+				dominated_by[first].push(second);
+				domination_count[second] += 1;
+			} else if dominates(points[second].as_ref(), points[first].as_ref()) {
+				dominated_by[second].push(first);
+				domination_count[first] += 1;
+			}
+		}
+	}
+
+	let mut result = Vec::new();
+	let mut front = (0..points.len()).filter(|&index| domination_count[index] == 0).collect::<Vec<_>>();
+	while !front.is_empty() {
+		let mut next = Vec::new();
+		for &index in &front {
+			for &dominated in &dominated_by[index] {
+				domination_count[dominated] -= 1;
+				if domination_count[dominated] == 0 {
+					next.push(dominated);
+				}
+			}
+		}
+		result.push(front);
+		front = next;
+	}
+	return result;
+}
+
+/// Crowding distance of every point of the front, in the order of the front. Points at the ends of any objective get infinity
+pub fn crowding_distance<P: AsRef<[f64]>>(points: &[P], front: &[usize]) -> Vec<f64> {
+	let mut result = vec![0.0; front.len()];
+	let Some(&first) = front.first() else {
+		return result;
+	};
+	for objective in 0..points[first].as_ref().len() {
+		let value = |position: usize| points[front[position]].as_ref()[objective];
+		let mut sorted = (0..front.len()).collect::<Vec<_>>();
+		sorted.sort_by(|&a, &b| fitness::compare(value(a), value(b)));
+		let range = value(sorted[sorted.len() - 1]) - value(sorted[0]);
+		result[sorted[0]] = f64::INFINITY;
+		result[sorted[sorted.len() - 1]] = f64::INFINITY;
+		// all points share the value, so the objective does not separate them
+		if range <= 0.0 {
+			continue;
+		}
+		for window in sorted.windows(3) {
+			result[window[1]] += (value(window[2]) - value(window[0])) / range;
+		}
+	}
+	return result;
+}
+
+/// Volume of the objective space dominated by the front and bounded by the reference point, larger is better.
+/// Points not strictly better than the reference in every objective do not contribute
+pub fn hypervolume<P: AsRef<[f64]>>(front: &[P], reference: &[f64]) -> f64 {
+	let points = front.iter().map(AsRef::as_ref).filter(|point| point.iter().zip(reference).all(|(a, limit)| a < limit)).collect::<Vec<_>>();
+	return sliced_volume(points, reference, reference.len());
+}
+
+/// Hypervolume by slicing along the last of the objectives, exponential in the objective count but exact
+fn sliced_volume(mut points: Vec<&[f64]>, reference: &[f64], objectives: usize) -> f64 {
+	if points.is_empty() {
+		return 0.0;
+	}
+	let last = objectives - 1;
+	if last == 0 {
+		return reference[0] - points.iter().map(|point| point[0]).fold(f64::INFINITY, f64::min);
+	}
+	points.sort_by(|a, b| fitness::compare(a[last], b[last]));
+	let mut volume = 0.0;
+	for index in 0..points.len() {
+		let next = points.get(index + 1).map_or(reference[last], |point| point[last]);
+		if next > points[index][last] {
+			volume += (next - points[index][last]) * sliced_volume(points[..=index].to_vec(), reference, last);
+		}
+	}
+	return volume;
+}
+
+/// Mean distance from every point of the reference front to the nearest point of the front, lower is better.
+/// Measures both the convergence and the spread of the front
+pub fn inverted_generational_distance<P: AsRef<[f64]>, R: AsRef<[f64]>>(front: &[P], reference_front: &[R]) -> f64 {
+	let total = reference_front.iter().map(|reference| {
+		return front.iter().map(|point| {
+			return point.as_ref().iter().zip(reference.as_ref()).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt();
+		}).fold(f64::INFINITY, f64::min);
+	}).sum::<f64>();
+	return total / reference_front.len() as f64;
+}
+
+/// Point of the search space together with its objective values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<V: Vector> {
+	pub position: V,
+	pub values: Vec<f64>,
+}
+
+impl<V: Vector> AsRef<[f64]> for Solution<V> {
+	fn as_ref(&self) -> &[f64] {
+		return &self.values;
+	}
+}
+
+/// Bounded set of mutually non-dominated solutions. When full, the most crowded members are dropped
+#[derive(Debug, Clone)]
+pub struct Archive<V: Vector> {
+	capacity: usize,
+	members: Vec<Solution<V>>,
+}
+
+impl<V: Vector> Archive<V> {
+	pub fn new(capacity: usize) -> Self {
+		return Self {
+			capacity,
+			members: Vec::with_capacity(capacity + 1),
+		};
+	}
+
+	pub fn members(&self) -> &[Solution<V>] {
+		return &self.members;
+	}
+
+	/// Objective values of the members
+	pub fn front(&self) -> Vec<Vec<f64>> {
+		return self.members.iter().map(|member| member.values.clone()).collect();
+	}
+
+	pub fn clear(&mut self) {
+		self.members.clear();
+	}
+
+	/// Adds the solution unless a member dominates it or has the same values, removing the members it dominates.
+	/// Returns whether the solution was added, it can still be dropped right away if the archive is full
+	pub fn insert(&mut self, solution: Solution<V>) -> bool {
+		if self.members.iter().any(|member| member.values == solution.values || dominates(&member.values, &solution.values)) {
+			return false;
+		}
+		self.members.retain(|member| !dominates(&solution.values, &member.values));
+		self.members.push(solution);
+		while self.members.len() > self.capacity {
+			let indices = (0..self.members.len()).collect::<Vec<_>>();
+			let distances = crowding_distance(&self.members, &indices);
+			let most_crowded = (0..distances.len()).min_by(|&a, &b| fitness::compare(distances[a], distances[b])).unwrap();
+			self.members.remove(most_crowded);
+		}
+		return true;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{pareto::{crowding_distance, dominates, hypervolume, inverted_generational_distance, non_dominated_sort, Archive, Solution}, vector::VectorN};
+
+	#[test]
+	fn sorting_test() {
+		let points = vec![vec![1.0, 4.0], vec![2.0, 2.0], vec![4.0, 1.0], vec![3.0, 3.0], vec![5.0, 5.0], vec![2.0, 2.0]];
+		assert!(dominates(&points[1], &points[3]));
+		assert!(!dominates(&points[1], &points[5]));
+		assert!(!dominates(&points[0], &points[2]));
+		assert_eq!(non_dominated_sort(&points), vec![vec![0, 1, 2, 5], vec![3], vec![4]]);
+
+		let distances = crowding_distance(&points, &[0, 1, 2]);
+		assert_eq!(distances[0], f64::INFINITY);
+		assert_eq!(distances[2], f64::INFINITY);
+		assert!((distances[1] - 2.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn indicators_test() {
+		let front = vec![vec![1.0, 3.0], vec![2.0, 2.0], vec![3.0, 1.0]];
+		assert!((hypervolume(&front, &[4.0, 4.0]) - 6.0).abs() < 1e-12);
+		// points outside the reference box and dominated points add nothing
+		let mut extended = front.clone();
+		extended.extend([vec![5.0, 0.0], vec![2.5, 2.5]]);
+		assert!((hypervolume(&extended, &[4.0, 4.0]) - 6.0).abs() < 1e-12);
+		assert!((hypervolume(&[vec![0.0, 0.0, 0.0]], &[1.0, 2.0, 3.0]) - 6.0).abs() < 1e-12);
+		assert!((hypervolume(&[vec![0.0, 0.0, 1.0], vec![1.0, 1.0, 0.0]], &[2.0, 2.0, 2.0]) - 5.0).abs() < 1e-12);
+
+		assert_eq!(inverted_generational_distance(&front, &front), 0.0);
+		assert!((inverted_generational_distance(&front[..1], &[vec![1.0, 3.0], vec![4.0, 7.0]]) - 2.5).abs() < 1e-12);
+	}
+
+	#[test]
+	fn archive_test() {
+		let solution = |values: Vec<f64>| Solution { position: VectorN::new([values[0]]), values };
+		let mut archive = Archive::new(3);
+		assert!(archive.insert(solution(vec![2.0, 2.0])));
+		assert!(!archive.insert(solution(vec![3.0, 3.0])));
+		assert!(!archive.insert(solution(vec![2.0, 2.0])));
+		assert!(archive.insert(solution(vec![0.0, 4.0])));
+		assert!(archive.insert(solution(vec![4.0, 0.0])));
+		assert!(archive.insert(solution(vec![1.0, 1.0])));
+		assert_eq!(archive.front(), vec![vec![0.0, 4.0], vec![4.0, 0.0], vec![1.0, 1.0]]);
+		// the archive is full and the new point is the most crowded one, so it is dropped again
+		assert!(archive.insert(solution(vec![0.5, 1.5])));
+		assert_eq!(archive.front(), vec![vec![0.0, 4.0], vec![4.0, 0.0], vec![1.0, 1.0]]);
+	}
+}
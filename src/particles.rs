@@ -1,269 +1,293 @@
 use rand::prelude::*;
 
-use crate::{functions::Functions, vector::VectorN};
+use std::sync::Arc;
+
+use crate::{bounds::Bounds, constraints::{Evaluation, Ranking}, error::{self, SlimeError}, fitness::{self, Direction, FitnessSettings}, objective::{EvaluationCounter, Objective}, optimizer::{Candidate, Optimizer, PopulationOptimizer}, random::RandomSource, vector::Vector};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Coefficients {
+	pub social: f64,
+	pub cognitive: f64,
+	pub inertia: f64,
+}
 
 #[derive(Debug, Clone)]
-pub struct Particle<const N: usize> {
-	pub current_speed: VectorN<N>,
-	pub coordinates: VectorN<N>,
-	pub best_found_solution: VectorN<N>, // of this particle
+pub struct Particle<V: Vector> {
+	pub current_speed: V,
+	pub coordinates: V,
+	pub best_found_solution: V, // of this particle
 	best_found_solution_value: f64,
-	pub bounds: (f64, f64), // lower, upper
+	best_found_violation: f64,
+	pub bounds: Bounds<V>,
 	pub social_coefficient: f64,
 	pub cognitive_coefficient: f64,
 	pub inertia_coefficient: f64,
-	function: Functions<N>,
 	function_value: f64,
+	violation: f64,
 }
 
-impl<const N: usize> Particle<N> {
-	fn move_particle(&mut self, best_global_solution: VectorN<N>, random_source: &mut ThreadRng) {
-		let inertia_part = self.current_speed * self.inertia_coefficient;
-		let social_part = (best_global_solution - self.coordinates) * self.social_coefficient * random_source.gen::<f64>();
-		let self_part = (self.best_found_solution - self.coordinates) * self.cognitive_coefficient * random_source.gen::<f64>();
+impl<V: Vector> Particle<V> {
+	pub fn evaluation(&self) -> Evaluation {
+		return Evaluation::new(self.function_value, self.violation);
+	}
+
+	fn best_found_evaluation(&self) -> Evaluation {
+		return Evaluation::new(self.best_found_solution_value, self.best_found_violation);
+	}
+
+	fn move_particle(&mut self, best_global_solution: &V, random_source: &mut impl Rng) {
+		let inertia_part = self.current_speed.clone() * self.inertia_coefficient;
+		let social_part = (best_global_solution.clone() - self.coordinates.clone()) * self.social_coefficient * random_source.gen::<f64>();
+		let self_part = (self.best_found_solution.clone() - self.coordinates.clone()) * self.cognitive_coefficient * random_source.gen::<f64>();
 		self.current_speed = inertia_part + social_part + self_part;
-		self.coordinates += self.current_speed * 1.0;
+		self.coordinates += self.current_speed.clone() * 1.0;
 
-		self.coordinates.clamp(self.bounds);
+		self.coordinates.clamp(&self.bounds);
+	}
 
-		self.function_value = (self.function)(self.coordinates);
+	fn evaluate(&mut self, function: &mut EvaluationCounter<V>, random_source: &mut impl Rng) -> Result<(), SlimeError> {
+		let evaluation = function.evaluate_constrained(&mut self.coordinates, &self.bounds, random_source)?;
+		self.function_value = evaluation.value;
+		self.violation = evaluation.violation;
+		return Ok(());
 	}
 }
 
-impl<const N: usize> PartialEq for Particle<N> {
+impl<V: Vector> PartialEq for Particle<V> {
 	fn eq(&self, other: &Self) -> bool {
 		return self.coordinates == other.coordinates;
 	}
 }
 
-impl<const N: usize> PartialOrd for Particle<N> {
+/// Orders by objective value, regardless of the optimization direction
+impl<V: Vector> PartialOrd for Particle<V> {
 	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-		return self.function_value.partial_cmp(&other.function_value);
+		return Some(fitness::compare(self.function_value, other.function_value));
 	}
 }
 
-#[derive(Debug, Clone)]
-pub struct WorldState<const DIMENSIONS: usize> {
-	pub particles: Vec<Particle<DIMENSIONS>>,
-	pub function: Functions<DIMENSIONS>,
-	pub best_solution: VectorN<DIMENSIONS>,
+impl<V: Vector> Candidate<V> for Particle<V> {
+	fn position(&self) -> &V {
+		return &self.coordinates;
+	}
+
+	fn fitness(&self) -> f64 {
+		return self.function_value;
+	}
+
+	fn violation(&self) -> f64 {
+		return self.violation;
+	}
+}
+
+#[derive(Clone)]
+pub struct WorldState<V: Vector> {
+	pub particles: Vec<Particle<V>>,
+	pub function: EvaluationCounter<V>,
+	pub best_solution: V,
 	pub best_solution_value: f64,
-	bounds: (f64, f64),
+	best_violation: f64,
+	/// Number of evaluations averaged into the best solution value
+	best_samples: usize,
+	bounds: Bounds<V>,
+	dimensions: usize,
 	particle_count: usize,
-	social_coefficient: f64,
-	cognitive_coefficient: f64,
-	inertia_coefficient: f64,
-	random_generator: ThreadRng,
+	coefficients: Coefficients,
+	iteration_count: usize,
+	current_iteration: usize,
+	random_generator: RandomSource,
 }
 
-impl<const DIMENSIONS: usize> WorldState<DIMENSIONS> {
-	pub fn new(particle_count: usize, function: Functions<DIMENSIONS>, bounds: (f64, f64), social_coefficient: f64, cognitive_coefficient: f64, inertia_coefficient: f64) -> Self {
-		if bounds.0 >= bounds.1 {
-			panic!("Incorrect order of bounds or zero size");
-		}
+impl<V: Vector> WorldState<V> {
+	pub fn new(particle_count: usize, function: Arc<dyn Objective<V>>, dimensions: usize, iteration_count: usize, coefficients: Coefficients, fitness_settings: FitnessSettings, rng_source: RandomSource) -> Result<Self, SlimeError> {
+		let bounds = function.bounds(dimensions);
+		error::validate_population(particle_count, iteration_count, &bounds, dimensions)?;
 		let mut result = Self {
-			random_generator: thread_rng(),
+			random_generator: rng_source,
 			particles: Vec::with_capacity(particle_count),
-			function,
-			best_solution: VectorN::default(),
-			best_solution_value: f64::INFINITY,
+			function: EvaluationCounter::new(function).with_settings(fitness_settings),
+			best_solution: V::zeros(dimensions),
+			best_solution_value: fitness_settings.direction.worst(),
+			best_violation: f64::INFINITY,
+			best_samples: 1,
 			bounds,
+			dimensions,
 			particle_count,
-			social_coefficient,
-			cognitive_coefficient,
-			inertia_coefficient,
+			coefficients,
+			iteration_count,
+			current_iteration: 0,
 		};
 
-		result.create_particles();
+		result.create_particles()?;
 
-		return result;
+		return Ok(result);
 	}
 
-	fn create_particles(&mut self) {
-		let size = self.bounds.1 - self.bounds.0;
-		let mut best_solution = f64::INFINITY;
+	fn create_particles(&mut self) -> Result<(), SlimeError> {
 		for _ in 0..self.particle_count {
-			let mut coords = [0.0; DIMENSIONS];
-			coords.fill_with(|| self.random_generator.gen::<f64>() * size + self.bounds.0);
-			let value_at_coords = (self.function)(VectorN::new(coords));
+			let mut coords = self.bounds.sample(&mut self.random_generator);
+			let evaluation = self.function.evaluate_constrained(&mut coords, &self.bounds, &mut self.random_generator)?;
 			self.particles.push(Particle {
-				current_speed: VectorN::default(),
-				coordinates: VectorN::new(coords),
-				best_found_solution: VectorN::new(coords),
-				best_found_solution_value: value_at_coords,
-				bounds: self.bounds,
-				social_coefficient: self.social_coefficient,
-				cognitive_coefficient: self.cognitive_coefficient,
-				inertia_coefficient: self.inertia_coefficient,
-				function: self.function,
-				function_value: (self.function)(VectorN::new(coords)),
+				current_speed: V::zeros(self.dimensions),
+				coordinates: coords.clone(),
+				best_found_solution: coords,
+				best_found_solution_value: evaluation.value,
+				best_found_violation: evaluation.violation,
+				bounds: self.bounds.clone(),
+				social_coefficient: self.coefficients.social,
+				cognitive_coefficient: self.coefficients.cognitive,
+				inertia_coefficient: self.coefficients.inertia,
+				function_value: evaluation.value,
+				violation: evaluation.violation,
 			});
-			if value_at_coords < best_solution {
-				best_solution = value_at_coords;
-				self.best_solution = VectorN::new(coords);
-			}
 		}
+		self.start();
+		return Ok(());
 	}
 
-	pub fn reset(&mut self) {
-		let size = self.bounds.1 - self.bounds.0;
-		let mut best_solution = f64::INFINITY;
-		for particle in &mut self.particles {
-			let mut coords = [0.0; DIMENSIONS];
-			coords.fill_with(|| self.random_generator.gen::<f64>() * size + self.bounds.0);
-			particle.current_speed = VectorN::default();
-			particle.coordinates = VectorN::new(coords);
-			particle.best_found_solution = VectorN::new(coords);
-			let particle_solution = (self.function)(VectorN::new(coords));
-			if particle_solution < best_solution {
-				best_solution = particle_solution;
-				self.best_solution = VectorN::new(coords);
-				self.best_solution_value = (self.function)(self.best_solution);
+	/// Initializes the constraint handling and the best solution from a new swarm
+	fn start(&mut self) {
+		self.function.constraint_state().start(self.particles.iter().map(|particle| particle.violation));
+		let ranking = self.function.ranking();
+		self.best_solution_value = ranking.direction.worst();
+		self.best_violation = f64::INFINITY;
+		self.best_samples = 1;
+		for particle in &self.particles {
+			if ranking.is_better(particle.evaluation(), self.best_evaluation()) {
+				self.best_solution = particle.coordinates.clone();
+				self.best_solution_value = particle.function_value;
+				self.best_violation = particle.violation;
 			}
 		}
 	}
 
-	pub fn set_coeffs(&mut self, social_coefficient: f64, cognitive_coefficient: f64, inertia_coefficient: f64) {
-		self.social_coefficient = social_coefficient;
-		self.cognitive_coefficient = cognitive_coefficient;
-		self.inertia_coefficient = inertia_coefficient;
+	fn best_evaluation(&self) -> Evaluation {
+		return Evaluation::new(self.best_solution_value, self.best_violation);
+	}
+
+	pub fn set_coeffs(&mut self, coefficients: Coefficients) {
+		self.coefficients = coefficients;
 		for particle in &mut self.particles {
-			particle.social_coefficient = social_coefficient;
-			particle.cognitive_coefficient = cognitive_coefficient;
-			particle.inertia_coefficient = inertia_coefficient;
+			particle.social_coefficient = coefficients.social;
+			particle.cognitive_coefficient = coefficients.cognitive;
+			particle.inertia_coefficient = coefficients.inertia;
 		}
 	}
 
-	pub fn get_coeffs(&self) -> (f64, f64, f64) { // same order as set_coeffs
-		return (self.social_coefficient, self.cognitive_coefficient, self.inertia_coefficient);
+	pub fn get_coeffs(&self) -> Coefficients {
+		return self.coefficients;
 	}
 
 	pub fn update_best_solutions(&mut self) {
+		let ranking = self.function.ranking();
+		(self.best_solution_value, self.best_samples) = self.function.refine(&self.best_solution, self.best_solution_value, self.best_samples);
 		for particle in &mut self.particles {
-			let particle_solution = (self.function)(particle.coordinates);
-			if particle_solution < self.best_solution_value {
-				self.best_solution_value = particle_solution;
-				self.best_solution = particle.coordinates;
+			let evaluation = particle.evaluation();
+			if ranking.is_better(evaluation, Evaluation::new(self.best_solution_value, self.best_violation)) {
+				let (value, samples) = self.function.refine(&particle.coordinates, evaluation.value, 1);
+				if ranking.is_better(Evaluation::new(value, evaluation.violation), Evaluation::new(self.best_solution_value, self.best_violation)) {
+					self.best_solution_value = value;
+					self.best_violation = evaluation.violation;
+					self.best_solution = particle.coordinates.clone();
+					self.best_samples = samples;
+				}
 			}
-			if particle_solution < particle.best_found_solution_value {
-				particle.best_found_solution = particle.coordinates;
-				particle.best_found_solution_value = particle_solution;
+			if ranking.is_better(evaluation, particle.best_found_evaluation()) {
+				particle.best_found_solution = particle.coordinates.clone();
+				particle.best_found_solution_value = evaluation.value;
+				particle.best_found_violation = evaluation.violation;
 			}
 		}
 	}
 
-	pub fn move_particles(&mut self) {
+	pub fn move_particles(&mut self) -> Result<(), SlimeError> {
 		for particle in &mut self.particles {
-			particle.move_particle(self.best_solution, &mut self.random_generator);
+			particle.move_particle(&self.best_solution, &mut self.random_generator);
+			particle.evaluate(&mut self.function, &mut self.random_generator)?;
 		}
+		return Ok(());
 	}
 
-	pub fn do_iteration(&mut self) {
-		self.move_particles();
+	pub fn do_iteration(&mut self) -> Result<(), SlimeError> {
+		self.move_particles()?;
 		self.update_best_solutions();
+		let best_is_feasible = self.best_evaluation().is_feasible();
+		self.function.constraint_state().update((self.current_iteration + 1) as f64 / self.iteration_count as f64, best_is_feasible);
+		return Ok(());
 	}
+}
 
-	pub fn do_all_iterations(&mut self, iteration_count: usize) {
-		for _ in 0..iteration_count {
-			self.do_iteration();
-		}
+impl<V: Vector> Optimizer<V> for WorldState<V> {
+	fn step(&mut self) -> Result<(), SlimeError> {
+		self.do_iteration()?;
+		self.current_iteration += 1;
+		return Ok(());
 	}
-}
 
-#[derive(Clone, Debug)]
-pub struct MultiSwarmWorldState<const N: usize> {
-	swarms: Vec<WorldState<N>>,
-	migration_threshold: f64,
-	pub best_solution: VectorN<N>,
-	pub best_solution_value: f64,
-}
+	fn current_iteration(&self) -> usize {
+		return self.current_iteration;
+	}
 
-impl<const N: usize> MultiSwarmWorldState<N> {
-	pub fn new(swarm_count: usize, migration_threshold: f64, particle_count: usize, function: Functions<N>, bounds: (f64, f64), social_coefficient: f64, cognitive_coefficient: f64, inertia_coefficient: f64) -> Self {
-		let mut swarms = Vec::with_capacity(swarm_count);
-		let mut best_solution = VectorN::default();
-		let mut best_solution_value = f64::MAX;
-		for _ in 0..swarm_count {
-			let world = WorldState::new(particle_count, function, bounds, social_coefficient, cognitive_coefficient, inertia_coefficient);
-			if world.best_solution_value < best_solution_value {
-				best_solution = world.best_solution;
-				best_solution_value = world.best_solution_value;
-			}
-			swarms.push(world);
+	fn iteration_count(&self) -> usize {
+		return self.iteration_count;
+	}
+
+	fn reset(&mut self) -> Result<(), SlimeError> {
+		self.function.reset_count();
+		for particle in &mut self.particles {
+			let mut coords = self.bounds.sample(&mut self.random_generator);
+			let evaluation = self.function.evaluate_constrained(&mut coords, &self.bounds, &mut self.random_generator)?;
+			particle.current_speed = V::zeros(self.dimensions);
+			particle.coordinates = coords.clone();
+			particle.best_found_solution = coords;
+			particle.function_value = evaluation.value;
+			particle.violation = evaluation.violation;
+			particle.best_found_solution_value = evaluation.value;
+			particle.best_found_violation = evaluation.violation;
 		}
+		self.start();
+		self.current_iteration = 0;
+		return Ok(());
+	}
 
-		return Self {
-			swarms, migration_threshold, best_solution, best_solution_value
-		};
+	fn direction(&self) -> Direction {
+		return self.function.settings().direction;
 	}
 
-	fn update_best_solutions(&mut self) {
-		for swarm in self.swarms.iter() {
-			if swarm.best_solution_value < self.best_solution_value {
-				self.best_solution = swarm.best_solution;
-				self.best_solution_value = swarm.best_solution_value;
-			}
-		}
+	fn best_solution(&self) -> V {
+		return self.best_solution.clone();
 	}
 
-	pub fn do_iteration(&mut self) {
-		for swarm in &mut self.swarms {
-			swarm.do_iteration();
-		}
-		// migration
-		let size = self.swarms.len();
-		// iterate over distinct pairs
-		// Can't use iterators as it requires mutable references to two swarm
-		// Will migrate best from both parts to one of the swarms and worst to the other
-		for first_index in 0..(size - 1) {
-			for second_index in (first_index + 1)..size {
-				let difference = (self.swarms[second_index].best_solution_value - self.swarms[first_index].best_solution_value).abs();
-				if difference > self.migration_threshold {
-					let lambda = difference / self.swarms[second_index].best_solution_value.max(self.swarms[first_index].best_solution_value);
-					let migration_count = (lambda * self.swarms[first_index].particles.len() as f64) as usize;
-
-					let mut sorted_first = self.swarms[first_index].particles.clone();
-					sorted_first.sort_by(|a, b| a.partial_cmp(b).unwrap()); // Worst are first
-
-					let mut sorted_second_reverse = self.swarms[second_index].particles.clone();
-					sorted_second_reverse.sort_by(|a, b| a.partial_cmp(b).unwrap().reverse()); // Best are first
-					
-					let mut migrated_first = sorted_first.split_off(migration_count); // contains best from first
-					let mut left_second = sorted_second_reverse.split_off(migration_count); // contains worst from second
-
-					// sorted_first now contains the unmigrated part of first swarm, so does left_second for the second swarm
-					// now merge the migrated and unmigrated parts
-
-					migrated_first.append(&mut sorted_second_reverse);
-					sorted_first.append(&mut left_second);
-
-					migrated_first.shuffle(&mut thread_rng());
-					sorted_first.shuffle(&mut thread_rng());
-
-					self.swarms[first_index].particles = migrated_first;
-					self.swarms[second_index].particles = sorted_first;
-
-					// update both best solutions
-					self.swarms[first_index].update_best_solutions();
-					self.swarms[second_index].update_best_solutions();
-				}
-			}
-		}
+	fn best_value(&self) -> f64 {
+		return self.best_solution_value;
+	}
 
-		self.update_best_solutions();
+	fn best_violation(&self) -> f64 {
+		return self.best_violation;
 	}
 
-	pub fn do_all_iters(&mut self, iterations: usize) {
-		for _ in 0..iterations {
-			self.do_iteration();
-		}
+	fn ranking(&self) -> Ranking {
+		return self.function.ranking();
 	}
 
-	pub fn reset(&mut self) {
-		self.best_solution_value = f64::MAX;
-		for swarm in &mut self.swarms {
-			swarm.reset();
-		}
+	fn evaluations(&self) -> usize {
+		return self.function.count();
+	}
+
+	fn candidates(&self) -> Vec<&dyn Candidate<V>> {
+		return self.particles.iter().map(|particle| particle as &dyn Candidate<V>).collect();
+	}
+}
+
+impl<V: Vector> PopulationOptimizer<V> for WorldState<V> {
+	type Individual = Particle<V>;
+
+	fn population(&self) -> &[Particle<V>] {
+		return &self.particles;
+	}
+
+	fn replace_population(&mut self, population: Vec<Particle<V>>) {
+		self.particles = population;
 		self.update_best_solutions();
 	}
 }
\ No newline at end of file
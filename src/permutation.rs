@@ -0,0 +1,113 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::{bounds::Bounds, fitness, objective::{Objective, Optimum}, vector::Vector};
+
+/// Range of the random keys. The slime mould contraction multiplies the whole position by a factor between -1 and 1,
+/// which keeps or reverses the order of keys centered at zero, but would collapse keys from `[0, 1]` into ties
+pub const KEY_LIMIT: f64 = 1.0;
+
+/// Function of an ordering of a fixed number of elements, minimized unless the fitness settings say otherwise
+pub trait PermutationObjective: Send + Sync {
+	/// The permutation contains every index below `size` exactly once
+	fn value(&self, permutation: &[usize]) -> f64;
+
+	fn size(&self) -> usize;
+
+	/// Name used when reporting results
+	fn name(&self) -> Option<&str> {
+		return None;
+	}
+
+	/// Best reachable value, if known
+	fn optimum(&self) -> Option<f64> {
+		return None;
+	}
+}
+
+/// Permutation objective evaluated on random keys, so the continuous optimizers and their settings can be reused.
+/// A vector is decoded by ordering the indices by their keys
+pub struct RandomKeys<V: Vector> {
+	inner: Arc<dyn PermutationObjective>,
+	vector: PhantomData<fn(&V)>,
+}
+
+impl<V: Vector> RandomKeys<V> {
+	pub fn new(inner: Arc<dyn PermutationObjective>) -> Self {
+		return Self {
+			inner,
+			vector: PhantomData,
+		};
+	}
+
+	/// Indices ordered by increasing key, equal keys keep the order of their indices
+	pub fn decode(keys: &V) -> Vec<usize> {
+		let keys = keys.as_slice();
+		let mut result = (0..keys.len()).collect::<Vec<_>>();
+		result.sort_by(|&a, &b| fitness::compare(keys[a], keys[b]));
+		return result;
+	}
+}
+
+impl<V: Vector> Objective<V> for RandomKeys<V> {
+	fn value(&self, input: &V) -> f64 {
+		return self.inner.value(&Self::decode(input));
+	}
+
+	fn bounds(&self, _dimensions: usize) -> Bounds<V> {
+		return Bounds::uniform(self.inner.size(), -KEY_LIMIT, KEY_LIMIT);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.inner.name();
+	}
+
+	fn optimum(&self, _dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum().map(|value| Optimum { value, location: None });
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::f64::consts::PI;
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, optimizer::Optimizer, permutation::{PermutationObjective, RandomKeys}, random::RandomSource, slime, tsplib::Tsp, vector::{DynVector, VectorN}};
+
+	/// Cities evenly spread on a circle, visiting them in order is the shortest tour
+	fn circle(cities: usize) -> Tsp {
+		let coordinates = (0..cities).map(|index| {
+			let angle = 2.0 * PI * index as f64 / cities as f64;
+			return (100.0 * angle.cos(), 100.0 * angle.sin());
+		}).collect::<Vec<_>>();
+		let distances = coordinates.iter().flat_map(|a| coordinates.iter().map(|b| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt())).collect();
+		let side = 200.0 * (PI / cities as f64).sin();
+		return Tsp::from_distances("circle", cities, distances).unwrap().with_optimum(side * cities as f64);
+	}
+
+	#[test]
+	fn decode_test() {
+		assert_eq!(RandomKeys::decode(&VectorN::new([0.3, -0.5, 0.9, 0.0])), vec![1, 3, 0, 2]);
+		assert_eq!(RandomKeys::decode(&VectorN::new([0.0, 0.0, -1.0])), vec![2, 0, 1]);
+		// negating the keys reverses the tour, which keeps its length
+		let tsp = circle(6);
+		let keys = VectorN::new([0.3, -0.5, 0.9, 0.0, 0.1, -0.2]);
+		let reversed = keys * -1.0;
+		assert_eq!(tsp.value(&RandomKeys::decode(&keys)), tsp.value(&RandomKeys::decode(&reversed)));
+	}
+
+	#[test]
+	fn permutation_slime_test() {
+		let tsp = Arc::new(circle(12));
+		let optimum = tsp.optimum().unwrap();
+		let mut world = slime::WorldState::<DynVector>::permutation(30, tsp.clone(), 300, 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap();
+		let initial = world.best_value();
+		let result = world.run().unwrap();
+		let tour = RandomKeys::decode(&result.best_solution);
+		assert_eq!(result.best_value, tsp.tour_length(&tour));
+		assert!(result.best_value < initial);
+		assert!(result.best_value < 1.5 * optimum, "found a tour of {} with the optimum {}", result.best_value, optimum);
+	}
+}
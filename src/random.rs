@@ -0,0 +1,14 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Random number generator used by the optimizers. Seeded instances produce the same sequence on every platform,
+/// so any run can be replayed from its seed
+pub type RandomSource = ChaCha8Rng;
+
+/// Generator for a single run of a batch. Every run gets its own stream of the master seed,
+/// so the outcome of a run depends only on the seed and its index, not on which thread executes it
+pub fn run_source(master_seed: u64, run_index: usize) -> RandomSource {
+	let mut result = RandomSource::seed_from_u64(master_seed);
+	result.set_stream(run_index as u64);
+	return result;
+}
@@ -1,111 +1,264 @@
-use rand_distr::{Distribution, Uniform};
 use rand::prelude::*;
 
-use crate::{functions::Functions, vector::VectorN};
+use std::sync::Arc;
+
+use crate::{binary::{self, Binarized, BinaryObjective, TransferFunction}, bounds::Bounds, constraints::{Evaluation, Ranking}, error::{self, SlimeError}, fitness::{self, Direction, FitnessSettings}, objective::{EvaluationCounter, Objective}, optimizer::{Candidate, Optimizer, PopulationOptimizer}, permutation::{PermutationObjective, RandomKeys}, random::RandomSource, vector::Vector};
+
+/// Movement rule of a single mold, shared by the single and the multi-objective variant.
+/// The last branch contracts the position towards the center if given, and towards the origin as in the original algorithm otherwise
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn move_position<V: Vector>(position: &mut V, bounds: &Bounds<V>, weight: f64, z_parameter: f64, a_param: f64, first: &V, second: &V, center: Option<&V>, best_fitness_gap: f64, iter_progress: f64, random_source: &mut impl Rng) {
+	// equation 2.7
+	if random_source.gen::<f64>() < z_parameter {
+		*position = bounds.sample(random_source);
+	} else {
+		let p_value = best_fitness_gap.tanh(); // equation 2.2
+		if random_source.gen::<f64>() < p_value {
+			// a reaches zero at the last iteration, leaving an empty range
+			let vb_param = if a_param > 0.0 { random_source.gen_range(-a_param..a_param) } else { 0.0 };
+			*position = first.clone() + (second.clone() * weight - first.clone()) * vb_param;
+		} else {
+			let range_size = 1.0 - iter_progress;
+			let factor = random_source.gen_range(-range_size..range_size);
+			match center {
+				Some(center) => *position = center.clone() + (position.clone() - center.clone()) * factor,
+				None => *position *= factor,
+			}
+		}
+	}
+
+	position.clamp(bounds);
+}
+
+/// Weights of the molds from their ranking keys, lower keys being better
+pub(crate) fn weights(keys: &[f64], random_source: &mut impl Rng) -> Vec<f64> {
+	let mut result = vec![0.0; keys.len()];
+	let mut sorted = (0..keys.len()).collect::<Vec<_>>();
+	sorted.sort_unstable_by(|&first, &second| fitness::compare(keys[first], keys[second]));
+	let best_value_in_iter = keys[sorted[0]];
+	let worst_value_in_iter = keys[sorted[sorted.len() - 1]];
+	for (index, &sorted) in sorted.iter().enumerate() {
+		// a population of equal values would divide zero by zero
+		let relative = if best_value_in_iter == worst_value_in_iter {
+			0.0
+		} else {
+			(best_value_in_iter - keys[index]) / (best_value_in_iter - worst_value_in_iter)
+		};
+		let part = random_source.gen::<f64>() * (relative + 1.0).log10(); // Should this be a log10?
+		if index < keys.len() {
+			result[sorted] = 1.0 + part;
+		} else {
+			result[sorted] = 1.0 - part;
+		}
+	}
+	return result;
+}
 
 #[derive(Debug, Clone)]
-pub struct Slime<const N: usize> {
-	position: VectorN<N>,
+pub struct Slime<V: Vector> {
+	position: V,
+	/// Zeros and ones obtained from the position through the transfer function, evaluated instead of it in binary mode
+	bits: Option<V>,
+	transfer: Option<TransferFunction>,
 	weight: f64,
-	optimization_function: Functions<N>,
-	function_bounds: (f64, f64),
+	function_bounds: Bounds<V>,
 	function_value: f64,
+	violation: f64,
 	z_parameter: f64,
 }
 
-impl<const N: usize> PartialEq for Slime<N> {
+impl<V: Vector> PartialEq for Slime<V> {
 	fn eq(&self, other: &Self) -> bool {
 		return self.position == other.position;
 	}
 }
 
-impl<const N: usize> PartialOrd for Slime<N> {
+/// Orders by objective value, regardless of the optimization direction
+impl<V: Vector> PartialOrd for Slime<V> {
 	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-		return self.function_value.partial_cmp(&other.function_value);
+		return Some(fitness::compare(self.function_value, other.function_value));
 	}
 }
 
-impl<const N: usize> Slime<N> {
-	pub fn new(function_bounds: (f64, f64), optimization_function: Functions<N>, z_parameter: f64, random_source: &mut ThreadRng) -> Self {
-		let mut coords_array = [0.0; N];
-		let range = Uniform::from(function_bounds.0..function_bounds.1);
-		coords_array.fill_with(|| range.sample(random_source));
-		let position = VectorN::new(coords_array);
-		let function_value = optimization_function(position);
+impl<V: Vector> Candidate<V> for Slime<V> {
+	fn position(&self) -> &V {
+		return self.decision();
+	}
+
+	fn fitness(&self) -> f64 {
+		return self.function_value;
+	}
+
+	fn violation(&self) -> f64 {
+		return self.violation;
+	}
+}
+
+impl<V: Vector> Slime<V> {
+	pub fn new(function_bounds: &Bounds<V>, optimization_function: &mut EvaluationCounter<V>, z_parameter: f64, random_source: &mut impl Rng) -> Result<Self, SlimeError> {
+		let mut position = function_bounds.sample(random_source);
+		let evaluation = optimization_function.evaluate_constrained(&mut position, function_bounds, random_source)?;
+
+		return Ok(Self {
+			function_bounds: function_bounds.clone(), position, z_parameter,
+			bits: None,
+			transfer: None,
+			function_value: evaluation.value,
+			violation: evaluation.violation,
+			weight: 0.0,
+		});
+	}
 
-		return Self {
-			function_bounds, function_value, optimization_function, position, z_parameter,
+	/// Mold of the binary mode, moving in `[-POSITION_LIMIT, POSITION_LIMIT]` and starting with random bits
+	pub fn binary(dimensions: usize, optimization_function: &mut EvaluationCounter<V>, z_parameter: f64, transfer: TransferFunction, random_source: &mut impl Rng) -> Result<Self, SlimeError> {
+		let mut result = Self {
+			function_bounds: Bounds::uniform(dimensions, -binary::POSITION_LIMIT, binary::POSITION_LIMIT),
+			position: V::zeros(dimensions),
+			bits: Some(V::zeros(dimensions)),
+			transfer: Some(transfer),
+			z_parameter,
+			function_value: 0.0,
+			violation: 0.0,
 			weight: 0.0,
 		};
+		result.reset(optimization_function, random_source)?;
+		return Ok(result);
 	}
 
-	pub fn move_slime(&mut self, a_param: f64, first_slime: &Self, second_slime: &Self, best_global_result: f64, iter_progress: f64, random_source: &mut ThreadRng) {
-		// equation 2.7
-		if random_source.gen::<f64>() < self.z_parameter {
-			let range = Uniform::from(self.function_bounds.0..self.function_bounds.1);
-			self.position.coordinates.fill_with(|| range.sample(random_source));
-		} else {
-			let p_value = (self.function_value - best_global_result).abs().tanh(); // equation 2.2
-			if random_source.gen::<f64>() < p_value {
-				let vb_param = random_source.gen_range(-a_param..a_param);
-				self.position = first_slime.position + (second_slime.position * self.weight - first_slime.position) * vb_param;
-			} else {
-				let range_size = 1.0 - iter_progress;
-				self.position *= random_source.gen_range(-range_size..range_size);
-			}
-		}
+	/// Point the objective is evaluated at, the bits in binary mode and the position otherwise
+	pub fn decision(&self) -> &V {
+		return self.bits.as_ref().unwrap_or(&self.position);
+	}
+
+	pub fn evaluation(&self) -> Evaluation {
+		return Evaluation::new(self.function_value, self.violation);
+	}
 
-		self.position.clamp(self.function_bounds);
+	/// The approach of equation 2.1 starts from the best mold of the population
+	/// The gap is the absolute difference between the fitness of this mold and the best fitness, as ranking keys
+	/// In binary mode the bits are updated from the new position
+	pub fn move_slime(&mut self, a_param: f64, best_slime: &Self, other_slime: &Self, best_fitness_gap: f64, iter_progress: f64, random_source: &mut impl Rng) {
+		move_position(&mut self.position, &self.function_bounds, self.weight, self.z_parameter, a_param, &best_slime.position, &other_slime.position, None, best_fitness_gap, iter_progress, random_source);
+		if let (Some(transfer), Some(bits)) = (self.transfer, self.bits.as_mut()) {
+			transfer.update(bits, &self.position, random_source);
+		}
+	}
 
-		self.function_value = (self.optimization_function)(self.position);
+	pub fn evaluate(&mut self, optimization_function: &mut EvaluationCounter<V>, random_source: &mut impl Rng) -> Result<(), SlimeError> {
+		let evaluation = match self.bits.as_mut() {
+			Some(bits) => {
+				let bit_bounds = Bounds::uniform(bits.dimensions(), 0.0, 1.0);
+				let evaluation = optimization_function.evaluate_constrained(bits, &bit_bounds, random_source)?;
+				// resampling after a non-finite value leaves fractions
+				bits.as_mut_slice().iter_mut().for_each(|bit| *bit = bit.round());
+				evaluation
+			}
+			None => optimization_function.evaluate_constrained(&mut self.position, &self.function_bounds, random_source)?,
+		};
+		self.function_value = evaluation.value;
+		self.violation = evaluation.violation;
+		return Ok(());
 	}
 
-	fn reset(&mut self, random_source: &mut ThreadRng) {
-		let range = Uniform::from(self.function_bounds.0..self.function_bounds.1);
-		self.position.coordinates.fill_with(|| range.sample(random_source));
+	fn reset(&mut self, optimization_function: &mut EvaluationCounter<V>, random_source: &mut impl Rng) -> Result<(), SlimeError> {
+		self.position = self.function_bounds.sample(random_source);
+		if let Some(bits) = self.bits.as_mut() {
+			bits.as_mut_slice().iter_mut().for_each(|bit| *bit = if random_source.gen::<bool>() { 1.0 } else { 0.0 });
+		}
 		self.weight = 0.0;
-		self.function_value = (self.optimization_function)(self.position);
+		return self.evaluate(optimization_function, random_source);
 	}
 }
 
-#[derive(Debug, Clone)]
-pub struct WorldState<const N: usize> {
-	population: Vec<Slime<N>>,
+#[derive(Clone)]
+pub struct WorldState<V: Vector> {
+	population: Vec<Slime<V>>,
+	optimization_function: EvaluationCounter<V>,
 	pub best_solution_value: f64,
-	pub best_solution: VectorN<N>,
+	pub best_solution: V,
+	best_violation: f64,
+	/// Number of evaluations averaged into the best solution value
+	best_samples: usize,
 	a_parameter: f64,
 	iteration_count: usize,
-	random_source: ThreadRng,
+	current_iteration: usize,
+	random_source: RandomSource,
 }
 
-impl<const N: usize> WorldState<N> {
-	pub fn new(pop_size: usize, function_bounds: (f64, f64), iteration_count: usize, optimization_function: Functions<N>, z_parameter: f64, mut rng_source: ThreadRng) -> Self {
-		let mut population = Vec::with_capacity(pop_size);
+impl<V: Vector> WorldState<V> {
+	pub fn new(pop_size: usize, optimization_function: Arc<dyn Objective<V>>, dimensions: usize, iteration_count: usize, z_parameter: f64, fitness_settings: FitnessSettings, rng_source: RandomSource) -> Result<Self, SlimeError> {
+		return Self::build(pop_size, optimization_function, dimensions, iteration_count, z_parameter, None, fitness_settings, rng_source);
+	}
 
-		let mut best_solution = Default::default();
-		let mut best_solution_value = f64::MAX;
+	/// Binary mode, the continuous positions of the molds are mapped to bits by the transfer function.
+	/// Best solutions and candidate positions are vectors of zeros and ones
+	pub fn binary(pop_size: usize, optimization_function: Arc<dyn BinaryObjective>, iteration_count: usize, z_parameter: f64, transfer: TransferFunction, fitness_settings: FitnessSettings, rng_source: RandomSource) -> Result<Self, SlimeError> {
+		let dimensions = optimization_function.bit_count();
+		return Self::build(pop_size, Arc::new(Binarized::new(optimization_function)), dimensions, iteration_count, z_parameter, Some(transfer), fitness_settings, rng_source);
+	}
 
-		for _ in 0..pop_size {
-			let candidate = Slime::new(function_bounds, optimization_function, z_parameter, &mut rng_source);
-			if candidate.function_value < best_solution_value {
-				best_solution = candidate.position;
-				best_solution_value = candidate.function_value;
-			}
-			population.push(candidate);
+	/// Permutation mode, the positions of the molds are random keys decoded into orderings by sorting.
+	/// Best solutions and candidate positions are the keys, see [`RandomKeys::decode`]
+	pub fn permutation(pop_size: usize, optimization_function: Arc<dyn PermutationObjective>, iteration_count: usize, z_parameter: f64, fitness_settings: FitnessSettings, rng_source: RandomSource) -> Result<Self, SlimeError> {
+		let dimensions = optimization_function.size();
+		return Self::build(pop_size, Arc::new(RandomKeys::new(optimization_function)), dimensions, iteration_count, z_parameter, None, fitness_settings, rng_source);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn build(pop_size: usize, optimization_function: Arc<dyn Objective<V>>, dimensions: usize, iteration_count: usize, z_parameter: f64, transfer: Option<TransferFunction>, fitness_settings: FitnessSettings, mut rng_source: RandomSource) -> Result<Self, SlimeError> {
+		let function_bounds = optimization_function.bounds(dimensions);
+		error::validate_population(pop_size, iteration_count, &function_bounds, dimensions)?;
+		if !(0.0..=1.0).contains(&z_parameter) {
+			return Err(SlimeError::InvalidZParameter(z_parameter));
 		}
 
+		let mut population = Vec::with_capacity(pop_size);
+		let mut optimization_function = EvaluationCounter::new(optimization_function).with_settings(fitness_settings);
+		for _ in 0..pop_size {
+			let mold = match transfer {
+				Some(transfer) => Slime::binary(dimensions, &mut optimization_function, z_parameter, transfer, &mut rng_source)?,
+				None => Slime::new(&function_bounds, &mut optimization_function, z_parameter, &mut rng_source)?,
+			};
+			population.push(mold);
+		}
 
 		let mut result = Self {
-			population, best_solution, best_solution_value,
+			population, optimization_function,
+			best_solution: V::zeros(dimensions),
+			best_solution_value: fitness_settings.direction.worst(),
+			best_violation: f64::INFINITY,
+			best_samples: 1,
 			a_parameter: 0.0,
 			iteration_count,
+			current_iteration: 0,
 			random_source: rng_source,
 		};
 
+		result.start();
 		result.recalculate_a(0);
 		result.recalculate_weights();
 
-		return result;
+		return Ok(result);
+	}
+
+	/// Initializes the constraint handling and the best solution from a new population
+	fn start(&mut self) {
+		self.optimization_function.constraint_state().start(self.population.iter().map(|mold| mold.violation));
+		let ranking = self.optimization_function.ranking();
+		self.best_solution_value = ranking.direction.worst();
+		self.best_violation = f64::INFINITY;
+		self.best_samples = 1;
+		for mold in &self.population {
+			if ranking.is_better(mold.evaluation(), self.best_evaluation()) {
+				self.best_solution = mold.decision().clone();
+				self.best_solution_value = mold.function_value;
+				self.best_violation = mold.violation;
+			}
+		}
+	}
+
+	fn best_evaluation(&self) -> Evaluation {
+		return Evaluation::new(self.best_solution_value, self.best_violation);
 	}
 
 	fn recalculate_a(&mut self, iteration: usize) {
@@ -114,168 +267,124 @@ impl<const N: usize> WorldState<N> {
 	}
 
 	fn recalculate_weights(&mut self) {
-		let original_clone = self.population.clone();
-		let mut sorted = self.population.iter_mut().collect::<Vec<_>>();
-		sorted.sort_unstable_by(|first, second| {
-			return first.function_value.partial_cmp(&second.function_value).unwrap();
-		});
-		let best_value_in_iter = sorted[0].function_value;
-		let worst_value_in_iter = sorted[sorted.len() - 1].function_value;
-		for (index, (sorted, original)) in sorted.into_iter().zip(&original_clone).enumerate() {
-			let part = self.random_source.gen::<f64>() * (
-				(best_value_in_iter - original.function_value) / (best_value_in_iter - worst_value_in_iter) + 1.0
-			).log10(); // Should this be a log10?
-			if index < original_clone.len() {
-				sorted.weight = 1.0 + part;
-			} else {
-				sorted.weight = 1.0 - part;
-			}
+		// keys of the ranking reduce to the objective values, negated when maximizing, if no constraint is violated
+		let keys = self.optimization_function.ranking().keys(&self.population.iter().map(Slime::evaluation).collect::<Vec<_>>());
+		for (mold, weight) in self.population.iter_mut().zip(weights(&keys, &mut self.random_source)) {
+			mold.weight = weight;
 		}
 	}
 
 	fn update_best_solutions(&mut self) {
+		let ranking = self.optimization_function.ranking();
+		(self.best_solution_value, self.best_samples) = self.optimization_function.refine(&self.best_solution, self.best_solution_value, self.best_samples);
 		for mold in self.population.iter() {
-			if mold.function_value < self.best_solution_value {
-				self.best_solution = mold.position;
-				self.best_solution_value = mold.function_value;
+			if ranking.is_better(mold.evaluation(), self.best_evaluation()) {
+				let (value, samples) = self.optimization_function.refine(mold.decision(), mold.function_value, 1);
+				if ranking.is_better(Evaluation::new(value, mold.violation), self.best_evaluation()) {
+					self.best_solution = mold.decision().clone();
+					self.best_solution_value = value;
+					self.best_violation = mold.violation;
+					self.best_samples = samples;
+				}
 			}
 		}
 	}
 
-	fn do_iteration(&mut self, iter_number: usize) {
+	fn do_iteration(&mut self, iter_number: usize) -> Result<(), SlimeError> {
 		let iter_progress = iter_number as f64 / self.iteration_count as f64;
 		let original_clone = self.population.clone();
-		for mold in self.population.iter_mut() {
+		let mut evaluations = original_clone.iter().map(Slime::evaluation).collect::<Vec<_>>();
+		evaluations.push(self.best_evaluation());
+		let keys = self.optimization_function.ranking().keys(&evaluations);
+		let best_key = keys[keys.len() - 1];
+		// equation 2.1 approaches from the best mold, its position is the continuous one in the binary and permutation modes
+		let best_mold = &original_clone[(0..original_clone.len()).min_by(|&first, &second| fitness::compare(keys[first], keys[second])).unwrap()];
+		for (mold, key) in self.population.iter_mut().zip(&keys) {
 			mold.move_slime(
 				self.a_parameter,
+				best_mold,
 				original_clone.choose(&mut self.random_source).unwrap(),
-				original_clone.choose(&mut self.random_source).unwrap(),
-				self.best_solution_value,
+				(key - best_key).abs(),
 				iter_progress,
 				&mut self.random_source
 			);
+			mold.evaluate(&mut self.optimization_function, &mut self.random_source)?;
 		}
 		self.update_best_solutions();
+		let best_is_feasible = self.best_evaluation().is_feasible();
+		self.optimization_function.constraint_state().update((iter_number + 1) as f64 / self.iteration_count as f64, best_is_feasible);
 		self.recalculate_a(iter_number);
 		self.recalculate_weights();
+		return Ok(());
 	}
 
-	pub fn do_all_iters(&mut self) {
-		for iter in 0..self.iteration_count {
-			self.do_iteration(iter);
-		}
+}
+
+impl<V: Vector> Optimizer<V> for WorldState<V> {
+	fn step(&mut self) -> Result<(), SlimeError> {
+		self.do_iteration(self.current_iteration)?;
+		self.current_iteration += 1;
+		return Ok(());
+	}
+
+	fn current_iteration(&self) -> usize {
+		return self.current_iteration;
 	}
 
-	pub fn reset(&mut self) {
-		self.best_solution_value = f64::MAX;
+	fn iteration_count(&self) -> usize {
+		return self.iteration_count;
+	}
+
+	fn reset(&mut self) -> Result<(), SlimeError> {
+		self.optimization_function.reset_count();
 		for mold in &mut self.population {
-			mold.reset(&mut self.random_source);
-			if mold.function_value < self.best_solution_value {
-				self.best_solution_value = mold.function_value;
-				self.best_solution = mold.position;
-			}
+			mold.reset(&mut self.optimization_function, &mut self.random_source)?;
 		}
+		self.start();
+		self.current_iteration = 0;
 		self.recalculate_a(0);
-		self.recalculate_weights()
+		self.recalculate_weights();
+		return Ok(());
 	}
 
-}
+	fn direction(&self) -> Direction {
+		return self.optimization_function.settings().direction;
+	}
 
-#[derive(Clone, Debug)]
-pub struct MultiSwarmWorldState<const N: usize> {
-	swarms: Vec<WorldState<N>>,
-	iteration_count: usize,
-	migration_threshold: f64,
-	pub best_solution: VectorN<N>,
-	pub best_solution_value: f64,
-}
+	fn best_solution(&self) -> V {
+		return self.best_solution.clone();
+	}
 
-impl<const N: usize> MultiSwarmWorldState<N> {
-	pub fn new(swarm_count: usize, migration_threshold: f64, pop_size: usize, function_bounds: (f64, f64), iteration_count: usize, optimization_function: Functions<N>, z_parameter: f64) -> Self {
-		let mut swarms = Vec::with_capacity(swarm_count);
-		let mut best_solution = VectorN::default();
-		let mut best_solution_value = f64::MAX;
-		for _ in 0..swarm_count {
-			let world = WorldState::new(pop_size, function_bounds, iteration_count, optimization_function, z_parameter, thread_rng());
-			if world.best_solution_value < best_solution_value {
-				best_solution = world.best_solution;
-				best_solution_value = world.best_solution_value;
-			}
-			swarms.push(world);
-		}
+	fn best_value(&self) -> f64 {
+		return self.best_solution_value;
+	}
 
-		return Self {
-			swarms, iteration_count, migration_threshold, best_solution, best_solution_value
-		};
+	fn best_violation(&self) -> f64 {
+		return self.best_violation;
 	}
 
-	fn update_best_solutions(&mut self) {
-		for swarm in self.swarms.iter() {
-			if swarm.best_solution_value < self.best_solution_value {
-				self.best_solution = swarm.best_solution;
-				self.best_solution_value = swarm.best_solution_value;
-			}
-		}
+	fn ranking(&self) -> Ranking {
+		return self.optimization_function.ranking();
 	}
 
-	pub fn do_iteration(&mut self, iteration_number: usize) {
-		for swarm in &mut self.swarms {
-			swarm.do_iteration(iteration_number);
-		}
-		// migration
-		let size = self.swarms.len();
-		// iterate over distinct pairs
-		// Can't use iterators as it requires mutable references to two swarm
-		// Will migrate best from both parts to one of the swarms and worst to the other
-		for first_index in 0..(size - 1) {
-			for second_index in (first_index + 1)..size {
-				let difference = (self.swarms[second_index].best_solution_value - self.swarms[first_index].best_solution_value).abs();
-				if difference > self.migration_threshold {
-					let lambda = difference / self.swarms[second_index].best_solution_value.max(self.swarms[first_index].best_solution_value);
-					let migration_count = (lambda * self.swarms[first_index].population.len() as f64) as usize;
-
-					let mut sorted_first = self.swarms[first_index].population.clone();
-					sorted_first.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap()); // Worst are first
-
-					let mut sorted_second_reverse = self.swarms[second_index].population.clone();
-					sorted_second_reverse.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap().reverse()); // Best are first
-					
-					let mut migrated_first = sorted_first.split_off(migration_count); // contains best from first
-					let mut left_second = sorted_second_reverse.split_off(migration_count); // contains worst from second
-
-					// sorted_first now contains the unmigrated part of first swarm, so does left_second for the second swarm
-					// now merge the migrated and unmigrated parts
-
-					migrated_first.append(&mut sorted_second_reverse);
-					sorted_first.append(&mut left_second);
-
-					migrated_first.shuffle(&mut thread_rng());
-					sorted_first.shuffle(&mut thread_rng());
-
-					self.swarms[first_index].population = migrated_first;
-					self.swarms[second_index].population = sorted_first;
-
-					// update both best solutions
-					self.swarms[first_index].update_best_solutions();
-					self.swarms[second_index].update_best_solutions();
-				}
-			}
-		}
+	fn evaluations(&self) -> usize {
+		return self.optimization_function.count();
+	}
 
-		self.update_best_solutions();
+	fn candidates(&self) -> Vec<&dyn Candidate<V>> {
+		return self.population.iter().map(|mold| mold as &dyn Candidate<V>).collect();
 	}
+}
 
-	pub fn do_all_iters(&mut self) {
-		for iter in 0..self.iteration_count {
-			self.do_iteration(iter);
-		}
+impl<V: Vector> PopulationOptimizer<V> for WorldState<V> {
+	type Individual = Slime<V>;
+
+	fn population(&self) -> &[Slime<V>] {
+		return &self.population;
 	}
 
-	pub fn reset(&mut self) {
-		self.best_solution_value = f64::MAX;
-		for swarm in &mut self.swarms {
-			swarm.reset();
-		}
+	fn replace_population(&mut self, population: Vec<Slime<V>>) {
+		self.population = population;
 		self.update_best_solutions();
 	}
 }
\ No newline at end of file
@@ -0,0 +1,193 @@
+use std::time::{Duration, Instant};
+
+use crate::{constraints::{Evaluation, Ranking}, optimizer::Candidate, vector::Vector};
+
+/// Why a run has ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+	IterationLimit,
+	EvaluationLimit,
+	TargetReached,
+	Stagnation,
+	DiversityCollapse,
+	TimeLimit,
+	ObserverAbort,
+}
+
+impl std::fmt::Display for TerminationReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::IterationLimit => return write!(f, "iteration limit"),
+			Self::EvaluationLimit => return write!(f, "evaluation limit"),
+			Self::TargetReached => return write!(f, "target reached"),
+			Self::Stagnation => return write!(f, "stagnation"),
+			Self::DiversityCollapse => return write!(f, "diversity collapse"),
+			Self::TimeLimit => return write!(f, "time limit"),
+			Self::ObserverAbort => return write!(f, "observer"),
+		}
+	}
+}
+
+/// Conditions ending a run, checked after every iteration. The run stops as soon as any of them is met,
+/// and always after the optimizer's own iteration count
+#[derive(Debug, Clone, Default)]
+pub struct StoppingCriteria {
+	pub max_evaluations: Option<usize>,
+	/// Target objective value and the tolerance within which it is considered reached
+	pub target: Option<(f64, f64)>,
+	/// Number of iterations without improvement of the best value
+	pub stagnation_iterations: Option<usize>,
+	/// Lowest allowed mean distance of the population to its centroid
+	pub min_diversity: Option<f64>,
+	pub time_limit: Option<Duration>,
+}
+
+impl StoppingCriteria {
+	pub fn new() -> Self {
+		return Self::default();
+	}
+
+	pub fn with_max_evaluations(mut self, max_evaluations: usize) -> Self {
+		self.max_evaluations = Some(max_evaluations);
+		return self;
+	}
+
+	pub fn with_target(mut self, value: f64, tolerance: f64) -> Self {
+		self.target = Some((value, tolerance));
+		return self;
+	}
+
+	pub fn with_stagnation_iterations(mut self, iterations: usize) -> Self {
+		self.stagnation_iterations = Some(iterations);
+		return self;
+	}
+
+	pub fn with_min_diversity(mut self, diversity: f64) -> Self {
+		self.min_diversity = Some(diversity);
+		return self;
+	}
+
+	pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+		self.time_limit = Some(time_limit);
+		return self;
+	}
+}
+
+/// Progress of a run, updated after every iteration and checked against the criteria
+pub(crate) struct StoppingState {
+	start: Instant,
+	last_best: Evaluation,
+	iterations_without_improvement: usize,
+}
+
+impl StoppingState {
+	pub(crate) fn new(best: Evaluation) -> Self {
+		return Self {
+			start: Instant::now(),
+			last_best: best,
+			iterations_without_improvement: 0,
+		};
+	}
+
+	/// The ranking is passed on every check, because constraint handling strategies change it during the run
+	pub(crate) fn check<V: Vector>(&mut self, criteria: &StoppingCriteria, best: Evaluation, ranking: Ranking, evaluations: usize, population: &[&dyn Candidate<V>]) -> Option<TerminationReason> {
+		if ranking.is_better(best, self.last_best) {
+			self.last_best = best;
+			self.iterations_without_improvement = 0;
+		} else {
+			self.iterations_without_improvement += 1;
+		}
+
+		if let Some((target, tolerance)) = criteria.target {
+			// an infeasible solution does not reach the target, whatever its value
+			if best.is_feasible() && (best.value - target).abs() <= tolerance {
+				return Some(TerminationReason::TargetReached);
+			}
+		}
+		if criteria.max_evaluations.is_some_and(|max| evaluations >= max) {
+			return Some(TerminationReason::EvaluationLimit);
+		}
+		if criteria.stagnation_iterations.is_some_and(|max| self.iterations_without_improvement >= max) {
+			return Some(TerminationReason::Stagnation);
+		}
+		if criteria.min_diversity.is_some_and(|min| diversity(population) < min) {
+			return Some(TerminationReason::DiversityCollapse);
+		}
+		if criteria.time_limit.is_some_and(|limit| self.start.elapsed() >= limit) {
+			return Some(TerminationReason::TimeLimit);
+		}
+		return None;
+	}
+}
+
+/// Mean euclidean distance of the population members to their centroid
+pub fn diversity<V: Vector>(population: &[&dyn Candidate<V>]) -> f64 {
+	if population.is_empty() {
+		return 0.0;
+	}
+	let mut centroid = population[0].position().clone();
+	for member in &population[1..] {
+		centroid += member.position().clone();
+	}
+	centroid = centroid / population.len() as f64;
+
+	return population.iter().map(|member| {
+		return (member.position().clone() - centroid.clone()).as_slice().iter().map(|a| a.powi(2)).sum::<f64>().sqrt();
+	}).sum::<f64>() / population.len() as f64;
+}
+
+/// Iteration count spending the whole evaluation budget, for an optimizer evaluating `evaluations_per_iteration` points
+/// in every iteration and as many when creating the initial population
+pub fn iterations_for_budget(budget: usize, evaluations_per_iteration: usize) -> usize {
+	return budget.saturating_sub(evaluations_per_iteration) / evaluations_per_iteration;
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{fitness::FitnessSettings, functions::Functions, optimizer::Optimizer, random::RandomSource, slime, stopping::{iterations_for_budget, StoppingCriteria, TerminationReason}, vector::VectorN};
+
+	fn make_world() -> slime::WorldState<VectorN<3>> {
+		return slime::WorldState::new(10, Arc::new(Functions::Rastrigin), 3, 1000, 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap();
+	}
+
+	#[test]
+	fn iteration_limit_test() {
+		let result = make_world().run().unwrap();
+		assert_eq!(result.termination_reason, TerminationReason::IterationLimit);
+		assert_eq!(result.iterations, 1000);
+	}
+
+	#[test]
+	fn evaluation_limit_test() {
+		let result = make_world().run_until(&StoppingCriteria::new().with_max_evaluations(200)).unwrap();
+		assert_eq!(result.termination_reason, TerminationReason::EvaluationLimit);
+		assert_eq!(result.evaluations, 200);
+	}
+
+	#[test]
+	fn target_test() {
+		let result = make_world().run_until(&StoppingCriteria::new().with_target(0.0, 1.0)).unwrap();
+		assert_eq!(result.termination_reason, TerminationReason::TargetReached);
+		assert!(result.best_value <= 1.0);
+	}
+
+	#[test]
+	fn stagnation_test() {
+		let result = make_world().run_until(&StoppingCriteria::new().with_stagnation_iterations(5)).unwrap();
+		assert_eq!(result.termination_reason, TerminationReason::Stagnation);
+		assert!(result.iterations < 1000);
+	}
+
+	#[test]
+	fn budget_test() {
+		let budget = 1000;
+		let mut world = slime::WorldState::<VectorN<3>>::new(10, Arc::new(Functions::Rastrigin), 3, iterations_for_budget(budget, 10), 0.03, FitnessSettings::new(), RandomSource::seed_from_u64(0)).unwrap();
+		let result = world.run().unwrap();
+		assert_eq!(result.termination_reason, TerminationReason::IterationLimit);
+		assert_eq!(result.evaluations, budget);
+	}
+}
\ No newline at end of file
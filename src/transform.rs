@@ -0,0 +1,274 @@
+use std::sync::Arc;
+
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::{bounds::Bounds, error::SlimeError, objective::{Objective, Optimum}, vector::Vector};
+
+/// Share of the bounds in which randomly shifted optima are placed, keeping them away from the edges
+pub const SHIFT_FRACTION: f64 = 0.8;
+
+/// Orthogonal matrix in row major order, obtained by Gram-Schmidt orthonormalization of a matrix with normally distributed entries
+pub fn random_rotation(dimensions: usize, random_source: &mut impl Rng) -> Vec<f64> {
+	let mut rows: Vec<Vec<f64>> = Vec::with_capacity(dimensions);
+	while rows.len() < dimensions {
+		let mut row = (0..dimensions).map(|_| random_source.sample::<f64, _>(StandardNormal)).collect::<Vec<_>>();
+		for previous in &rows {
+			let projection = row.iter().zip(previous).map(|(a, b)| a * b).sum::<f64>();
+			row.iter_mut().zip(previous).for_each(|(a, b)| *a -= projection * b);
+		}
+		let norm = row.iter().map(|a| a.powi(2)).sum::<f64>().sqrt();
+		// nearly dependent rows are drawn again
+		if norm > 1e-8 {
+			rows.push(row.into_iter().map(|a| a / norm).collect());
+		}
+	}
+	return rows.concat();
+}
+
+/// Point the transformations are anchored at: the optimum if its location is known, the center of the bounds otherwise
+fn anchor<V: Vector>(objective: &dyn Objective<V>, dimensions: usize) -> V {
+	return objective.optimum(dimensions).and_then(|optimum| optimum.location).unwrap_or_else(|| {
+		let bounds = objective.bounds(dimensions);
+		return (bounds.lower + bounds.upper) / 2.0;
+	});
+}
+
+fn prefixed_name<V: Vector>(prefix: &str, objective: &dyn Objective<V>) -> Option<String> {
+	return objective.name().map(|name| format!("{}-{}", prefix, name));
+}
+
+/// Objective moved by a vector, `f(x - shift)`. The bounds stay the same
+pub struct Shifted<V: Vector> {
+	inner: Arc<dyn Objective<V>>,
+	shift: V,
+	name: Option<String>,
+}
+
+impl<V: Vector> Shifted<V> {
+	pub fn new(inner: Arc<dyn Objective<V>>, shift: V) -> Self {
+		return Self {
+			name: prefixed_name("shifted", inner.as_ref()),
+			inner, shift,
+		};
+	}
+
+	/// Moves the optimum, or the center if the optimum is unknown, to a random point in the middle `SHIFT_FRACTION` of the bounds
+	pub fn random(inner: Arc<dyn Objective<V>>, dimensions: usize, random_source: &mut impl Rng) -> Self {
+		let bounds = inner.bounds(dimensions);
+		let center = (bounds.lower.clone() + bounds.upper.clone()) / 2.0;
+		let half_range = (bounds.upper - bounds.lower) * (SHIFT_FRACTION / 2.0);
+		let target = Bounds::new(center.clone() - half_range.clone(), center + half_range).sample(random_source);
+		let shift = target - anchor(inner.as_ref(), dimensions);
+		return Self::new(inner, shift);
+	}
+
+	pub fn shift(&self) -> &V {
+		return &self.shift;
+	}
+}
+
+impl<V: Vector> Objective<V> for Shifted<V> {
+	fn value(&self, input: &V) -> f64 {
+		return self.inner.value(&(input.clone() - self.shift.clone()));
+	}
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		return self.inner.bounds(dimensions);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.name.as_deref();
+	}
+
+	fn optimum(&self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum(dimensions).map(|optimum| {
+			return Optimum {
+				value: optimum.value,
+				location: optimum.location.map(|location| location + self.shift.clone()),
+			};
+		});
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.inequality_constraints(&(input.clone() - self.shift.clone()));
+	}
+
+	fn equality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.equality_constraints(&(input.clone() - self.shift.clone()));
+	}
+}
+
+/// Objective rotated around its optimum, or around the center of the bounds if the optimum is unknown,
+/// `f(c + R(x - c))`. Makes separable functions non-separable while keeping the optimum in place
+pub struct Rotated<V: Vector> {
+	inner: Arc<dyn Objective<V>>,
+	rotation: Vec<f64>,
+	center: V,
+	name: Option<String>,
+}
+
+impl<V: Vector> Rotated<V> {
+	/// The matrix is given in row major order and has to be orthogonal for the optimum to stay in place
+	pub fn new(inner: Arc<dyn Objective<V>>, dimensions: usize, rotation: Vec<f64>) -> Result<Self, SlimeError> {
+		if rotation.len() != dimensions * dimensions {
+			return Err(SlimeError::InvalidTransform(format!("rotation matrix has {} entries instead of {}", rotation.len(), dimensions * dimensions)));
+		}
+		return Ok(Self {
+			name: prefixed_name("rotated", inner.as_ref()),
+			center: anchor(inner.as_ref(), dimensions),
+			inner, rotation,
+		});
+	}
+
+	pub fn random(inner: Arc<dyn Objective<V>>, dimensions: usize, random_source: &mut impl Rng) -> Self {
+		return Self::new(inner, dimensions, random_rotation(dimensions, random_source)).unwrap();
+	}
+
+	fn rotate(&self, input: &V) -> V {
+		let offset = input.clone() - self.center.clone();
+		let offset = offset.as_slice();
+		let mut rotated = self.center.clone();
+		for (row, coordinate) in self.rotation.chunks(offset.len()).zip(rotated.as_mut_slice()) {
+			*coordinate += row.iter().zip(offset).map(|(m, a)| m * a).sum::<f64>();
+		}
+		return rotated;
+	}
+}
+
+impl<V: Vector> Objective<V> for Rotated<V> {
+	fn value(&self, input: &V) -> f64 {
+		return self.inner.value(&self.rotate(input));
+	}
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		return self.inner.bounds(dimensions);
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.name.as_deref();
+	}
+
+	fn optimum(&self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum(dimensions);
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.inequality_constraints(&self.rotate(input));
+	}
+
+	fn equality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.equality_constraints(&self.rotate(input));
+	}
+}
+
+/// Objective with stretched coordinates, `f(x * factors)`. The bounds and the optimum are divided by the factors,
+/// so the search space grows with factors below 1 and shrinks with factors above 1
+pub struct Scaled<V: Vector> {
+	inner: Arc<dyn Objective<V>>,
+	factors: V,
+	name: Option<String>,
+}
+
+impl<V: Vector> Scaled<V> {
+	pub fn new(inner: Arc<dyn Objective<V>>, factors: V) -> Result<Self, SlimeError> {
+		if let Some(factor) = factors.as_slice().iter().find(|factor| !(factor.is_finite() && **factor > 0.0)) {
+			return Err(SlimeError::InvalidTransform(format!("scale factors must be positive, got {}", factor)));
+		}
+		return Ok(Self {
+			name: prefixed_name("scaled", inner.as_ref()),
+			inner, factors,
+		});
+	}
+
+	/// Same factor for every coordinate
+	pub fn uniform(inner: Arc<dyn Objective<V>>, dimensions: usize, factor: f64) -> Result<Self, SlimeError> {
+		let mut factors = V::zeros(dimensions);
+		factors.as_mut_slice().fill(factor);
+		return Self::new(inner, factors);
+	}
+
+	fn unscale(&self, mut point: V) -> V {
+		point.as_mut_slice().iter_mut().zip(self.factors.as_slice()).for_each(|(a, factor)| *a /= factor);
+		return point;
+	}
+}
+
+impl<V: Vector> Objective<V> for Scaled<V> {
+	fn value(&self, input: &V) -> f64 {
+		return self.inner.value(&(input.clone() * self.factors.clone()));
+	}
+
+	fn bounds(&self, dimensions: usize) -> Bounds<V> {
+		let bounds = self.inner.bounds(dimensions);
+		return Bounds::new(self.unscale(bounds.lower), self.unscale(bounds.upper));
+	}
+
+	fn name(&self) -> Option<&str> {
+		return self.name.as_deref();
+	}
+
+	fn optimum(&self, dimensions: usize) -> Option<Optimum<V>> {
+		return self.inner.optimum(dimensions).map(|optimum| {
+			return Optimum {
+				value: optimum.value,
+				location: optimum.location.map(|location| self.unscale(location)),
+			};
+		});
+	}
+
+	fn inequality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.inequality_constraints(&(input.clone() * self.factors.clone()));
+	}
+
+	fn equality_constraints(&self, input: &V) -> Vec<f64> {
+		return self.inner.equality_constraints(&(input.clone() * self.factors.clone()));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::Arc;
+
+	use rand::SeedableRng;
+
+	use crate::{functions::Functions, objective::Objective, random::RandomSource, transform::{random_rotation, Rotated, Scaled, Shifted}, vector::{Vector, VectorN}};
+
+	#[test]
+	fn rotation_is_orthogonal_test() {
+		let rotation = random_rotation(5, &mut RandomSource::seed_from_u64(0));
+		for first in 0..5 {
+			for second in 0..5 {
+				let product = (0..5).map(|index| rotation[first * 5 + index] * rotation[second * 5 + index]).sum::<f64>();
+				let expected = if first == second { 1.0 } else { 0.0 };
+				assert!((product - expected).abs() < 1e-12);
+			}
+		}
+	}
+
+	#[test]
+	fn composed_optimum_test() {
+		let mut random_source = RandomSource::seed_from_u64(0);
+		for function in [Functions::Rastrigin, Functions::Rosenbrock, Functions::Schwefel, Functions::Levy] {
+			let scaled: Arc<dyn Objective<VectorN<4>>> = Arc::new(Scaled::uniform(Arc::new(function), 4, 0.5).unwrap());
+			let rotated: Arc<dyn Objective<VectorN<4>>> = Arc::new(Rotated::random(scaled, 4, &mut random_source));
+			let shifted = Shifted::random(rotated, 4, &mut random_source);
+
+			let optimum = shifted.optimum(4).unwrap();
+			let location = optimum.location.unwrap();
+			assert!(shifted.bounds(4).contains(&location), "{:?} is outside the bounds", location);
+			assert_ne!(location, function.get_optimum(4).unwrap().location.unwrap());
+			assert!((shifted.value(&location) - optimum.value).abs() < 1e-6, "{} at its optimum is {}", Functions::name(function), shifted.value(&location));
+			assert_eq!(shifted.name(), Some(format!("shifted-rotated-scaled-{}", Functions::name(function)).as_str()));
+		}
+	}
+
+	#[test]
+	fn scaled_bounds_test() {
+		let scaled = Scaled::<VectorN<2>>::uniform(Arc::new(Functions::Rastrigin), 2, 2.0).unwrap();
+		assert_eq!(scaled.bounds(2).upper.as_slice(), &[2.56, 2.56]);
+		assert_eq!(scaled.value(&VectorN::new([0.5, 0.0])), Functions::Rastrigin.calculate(&[1.0, 0.0]));
+		assert!(Scaled::<VectorN<2>>::uniform(Arc::new(Functions::Rastrigin), 2, 0.0).is_err());
+		assert!(Rotated::<VectorN<2>>::new(Arc::new(Functions::Rastrigin), 2, vec![1.0]).is_err());
+	}
+}
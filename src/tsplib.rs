@@ -0,0 +1,313 @@
+use std::path::Path;
+
+use crate::{error::SlimeError, permutation::PermutationObjective};
+
+/// Radius of the idealized Earth used by the GEO distances, in kilometers
+const EARTH_RADIUS: f64 = 6378.388;
+
+/// Distance between two cities given by their coordinates
+type Distance = fn((f64, f64), (f64, f64)) -> f64;
+
+/// Travelling salesman problem, the length of the closed tour visiting every city is minimized
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tsp {
+	name: String,
+	size: usize,
+	/// Row major matrix, the distance from the city of the row to the city of the column
+	distances: Vec<f64>,
+	optimum: Option<f64>,
+}
+
+impl Tsp {
+	pub fn from_distances(name: impl Into<String>, size: usize, distances: Vec<f64>) -> Result<Self, SlimeError> {
+		if size < 2 || distances.len() != size * size {
+			return Err(SlimeError::InvalidBenchmarkData(format!("{} distances do not form a matrix of {} cities", distances.len(), size)));
+		}
+		return Ok(Self {
+			name: name.into(),
+			size, distances,
+			optimum: None,
+		});
+	}
+
+	/// Length of the best known tour, TSPLIB lists them for every instance
+	pub fn with_optimum(mut self, optimum: f64) -> Self {
+		self.optimum = Some(optimum);
+		return self;
+	}
+
+	/// Reads a TSPLIB instance. The optimum is taken from a `.opt.tour` file next to it if there is one
+	pub fn load(path: &Path) -> Result<Self, SlimeError> {
+		let mut result = Self::parse(&read_file(path)?)?;
+		let tour_path = path.with_extension("opt.tour");
+		if tour_path.exists() {
+			let length = result.tour_length(&parse_tour(&read_file(&tour_path)?, result.size)?);
+			result.optimum = Some(length);
+		}
+		return Ok(result);
+	}
+
+	/// Parses a TSPLIB instance of type TSP or ATSP with weights given explicitly or by EUC_2D, CEIL_2D, MAN_2D, MAX_2D, ATT or GEO coordinates
+	pub fn parse(text: &str) -> Result<Self, SlimeError> {
+		let mut name = String::new();
+		let mut size = None;
+		let mut weight_type = String::new();
+		let mut weight_format = "FULL_MATRIX".to_string();
+		let mut coordinates = Vec::new();
+		let mut weights = Vec::new();
+
+		let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+		while let Some(line) = lines.next() {
+			let (key, value) = match line.split_once(':') {
+				Some((key, value)) => (key.trim(), value.trim()),
+				None => (line, ""),
+			};
+			match key {
+				"NAME" => name = value.to_string(),
+				"TYPE" if value != "TSP" && value != "ATSP" => {
+					return Err(SlimeError::InvalidBenchmarkData(format!("TSPLIB type {} is not a travelling salesman problem", value)));
+				}
+				"DIMENSION" => size = Some(value.parse::<usize>().map_err(|_| SlimeError::InvalidBenchmarkData(format!("`{}` is not a dimension", value)))?),
+				"EDGE_WEIGHT_TYPE" => weight_type = value.to_string(),
+				"EDGE_WEIGHT_FORMAT" => weight_format = value.to_string(),
+				"NODE_COORD_SECTION" => {
+					let size = size.ok_or_else(|| missing("DIMENSION"))?;
+					for line in lines.by_ref().take(size) {
+						let numbers = parse_numbers(line.split_whitespace().skip(1).take(2), 2)?;
+						coordinates.push((numbers[0], numbers[1]));
+					}
+				}
+				"EDGE_WEIGHT_SECTION" => {
+					let size = size.ok_or_else(|| missing("DIMENSION"))?;
+					let count = explicit_count(&weight_format, size)?;
+					while weights.len() < count {
+						let line = lines.next().ok_or_else(|| SlimeError::InvalidBenchmarkData(format!("expected {} edge weights, found {}", count, weights.len())))?;
+						weights.extend(parse_numbers(line.split_whitespace(), line.split_whitespace().count())?);
+					}
+				}
+				"DISPLAY_DATA_SECTION" => {
+					lines.by_ref().take(size.ok_or_else(|| missing("DIMENSION"))?).for_each(drop);
+				}
+				"EOF" => break,
+				_ => {}
+			}
+		}
+
+		let size = size.ok_or_else(|| missing("DIMENSION"))?;
+		let distances = if weight_type == "EXPLICIT" {
+			explicit_matrix(&weight_format, size, &weights)?
+		} else {
+			if coordinates.len() != size {
+				return Err(SlimeError::InvalidBenchmarkData(format!("expected {} node coordinates, found {}", size, coordinates.len())));
+			}
+			let distance = coordinate_distance(&weight_type)?;
+			coordinates.iter().flat_map(|&a| coordinates.iter().map(move |&b| distance(a, b))).collect()
+		};
+		return Self::from_distances(name, size, distances);
+	}
+
+	pub fn name(&self) -> &str {
+		return &self.name;
+	}
+
+	pub fn size(&self) -> usize {
+		return self.size;
+	}
+
+	pub fn distance(&self, from: usize, to: usize) -> f64 {
+		return self.distances[from * self.size + to];
+	}
+
+	/// Length of the closed tour, returning from the last city to the first one
+	pub fn tour_length(&self, tour: &[usize]) -> f64 {
+		let closing = self.distance(tour[tour.len() - 1], tour[0]);
+		return tour.windows(2).map(|pair| self.distance(pair[0], pair[1])).sum::<f64>() + closing;
+	}
+}
+
+impl PermutationObjective for Tsp {
+	fn value(&self, permutation: &[usize]) -> f64 {
+		return self.tour_length(permutation);
+	}
+
+	fn size(&self) -> usize {
+		return self.size;
+	}
+
+	fn name(&self) -> Option<&str> {
+		return Some(&self.name).filter(|name| !name.is_empty()).map(String::as_str);
+	}
+
+	fn optimum(&self) -> Option<f64> {
+		return self.optimum;
+	}
+}
+
+/// Reads the cities of a TSPLIB tour file, numbered from zero
+pub fn load_tour(path: &Path, size: usize) -> Result<Vec<usize>, SlimeError> {
+	return parse_tour(&read_file(path)?, size);
+}
+
+/// Parses the TOUR_SECTION of a TSPLIB tour, which has to visit each of the cities exactly once
+pub fn parse_tour(text: &str, size: usize) -> Result<Vec<usize>, SlimeError> {
+	let mut tokens = text.split_whitespace().skip_while(|&token| token != "TOUR_SECTION").skip(1);
+	let mut result = Vec::with_capacity(size);
+	let mut visited = vec![false; size];
+	for token in tokens.by_ref().take_while(|&token| token != "-1" && token != "EOF") {
+		let city = token.parse::<usize>().ok().filter(|city| (1..=size).contains(city)).ok_or_else(|| {
+			return SlimeError::InvalidBenchmarkData(format!("`{}` is not a city of the tour", token));
+		})?;
+		if visited[city - 1] {
+			return Err(SlimeError::InvalidBenchmarkData(format!("city {} is visited twice", city)));
+		}
+		visited[city - 1] = true;
+		result.push(city - 1);
+	}
+	if result.len() != size {
+		return Err(SlimeError::InvalidBenchmarkData(format!("tour visits {} of {} cities", result.len(), size)));
+	}
+	return Ok(result);
+}
+
+fn read_file(path: &Path) -> Result<String, SlimeError> {
+	return std::fs::read_to_string(path).map_err(|error| SlimeError::InvalidBenchmarkData(format!("cannot read {}: {}", path.display(), error)));
+}
+
+fn missing(key: &str) -> SlimeError {
+	return SlimeError::InvalidBenchmarkData(format!("TSPLIB instance has no {} before its data", key));
+}
+
+fn parse_numbers<'a>(tokens: impl Iterator<Item = &'a str>, count: usize) -> Result<Vec<f64>, SlimeError> {
+	let values = tokens.take(count).map(|token| {
+		return token.parse::<f64>().map_err(|_| SlimeError::InvalidBenchmarkData(format!("`{}` is not a number", token)));
+	}).collect::<Result<Vec<_>, _>>()?;
+	if values.len() != count {
+		return Err(SlimeError::InvalidBenchmarkData(format!("expected {} values, found {}", count, values.len())));
+	}
+	return Ok(values);
+}
+
+/// Rounding to the nearest integer as done by the TSPLIB reference code
+fn nint(x: f64) -> f64 {
+	return (x + 0.5).floor();
+}
+
+fn coordinate_distance(weight_type: &str) -> Result<Distance, SlimeError> {
+	match weight_type {
+		"EUC_2D" => return Ok(|a, b| nint((a.0 - b.0).hypot(a.1 - b.1))),
+		"CEIL_2D" => return Ok(|a, b| (a.0 - b.0).hypot(a.1 - b.1).ceil()),
+		"MAN_2D" => return Ok(|a, b| nint((a.0 - b.0).abs() + (a.1 - b.1).abs())),
+		"MAX_2D" => return Ok(|a, b| nint((a.0 - b.0).abs()).max(nint((a.1 - b.1).abs()))),
+		"ATT" => {
+			return Ok(|a, b| {
+				let exact = (((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)) / 10.0).sqrt();
+				let rounded = nint(exact);
+				return if rounded < exact { rounded + 1.0 } else { rounded };
+			});
+		}
+		"GEO" => {
+			return Ok(|a, b| {
+				if a == b {
+					return 0.0;
+				}
+				let (latitude_a, longitude_a) = (geo_radians(a.0), geo_radians(a.1));
+				let (latitude_b, longitude_b) = (geo_radians(b.0), geo_radians(b.1));
+				let q1 = (longitude_a - longitude_b).cos();
+				let q2 = (latitude_a - latitude_b).cos();
+				let q3 = (latitude_a + latitude_b).cos();
+				return (EARTH_RADIUS * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos() + 1.0).floor();
+			});
+		}
+		_ => return Err(SlimeError::InvalidBenchmarkData(format!("unsupported TSPLIB edge weight type `{}`", weight_type))),
+	}
+}
+
+/// GEO coordinates are degrees with the minutes after the decimal point, the reference code uses pi rounded to 3.141592
+fn geo_radians(x: f64) -> f64 {
+	let degrees = x.trunc();
+	let minutes = x - degrees;
+	#[allow(clippy::approx_constant)]
+	return 3.141592 * (degrees + 5.0 * minutes / 3.0) / 180.0;
+}
+
+/// Number of weights stored by an explicit format
+fn explicit_count(format: &str, size: usize) -> Result<usize, SlimeError> {
+	match format {
+		"FULL_MATRIX" => return Ok(size * size),
+		"UPPER_ROW" | "LOWER_ROW" => return Ok(size * (size - 1) / 2),
+		"UPPER_DIAG_ROW" | "LOWER_DIAG_ROW" => return Ok(size * (size + 1) / 2),
+		_ => return Err(SlimeError::InvalidBenchmarkData(format!("unsupported TSPLIB edge weight format `{}`", format))),
+	}
+}
+
+fn explicit_matrix(format: &str, size: usize, weights: &[f64]) -> Result<Vec<f64>, SlimeError> {
+	if weights.len() != explicit_count(format, size)? {
+		return Err(SlimeError::InvalidBenchmarkData(format!("expected {} edge weights, found {}", explicit_count(format, size)?, weights.len())));
+	}
+	if format == "FULL_MATRIX" {
+		return Ok(weights.to_vec());
+	}
+	// the triangular formats list the pairs row by row, with or without the diagonal
+	let pairs = (0..size).flat_map(|row| {
+		return match format {
+			"UPPER_ROW" => (row + 1)..size,
+			"UPPER_DIAG_ROW" => row..size,
+			"LOWER_ROW" => 0..row,
+			_ => 0..(row + 1),
+		}.map(move |column| (row, column));
+	});
+	let mut result = vec![0.0; size * size];
+	for ((row, column), &weight) in pairs.zip(weights) {
+		result[row * size + column] = weight;
+		result[column * size + row] = weight;
+	}
+	return Ok(result);
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{permutation::PermutationObjective, tsplib::{parse_tour, Tsp}};
+
+	#[test]
+	fn coordinates_test() {
+		let text = "NAME : square\nCOMMENT : four cities\nTYPE : TSP\nDIMENSION : 4\nEDGE_WEIGHT_TYPE : EUC_2D\nNODE_COORD_SECTION\n1 0 0\n2 3 0\n3 3 4\n4 0 4\nEOF\n";
+		let tsp = Tsp::parse(text).unwrap();
+		assert_eq!(tsp.name(), "square");
+		assert_eq!(tsp.size(), 4);
+		assert_eq!(tsp.distance(0, 2), 5.0);
+		assert_eq!(tsp.tour_length(&[0, 1, 2, 3]), 14.0);
+		assert_eq!(tsp.value(&[0, 2, 1, 3]), 18.0);
+
+		let tour = parse_tour("NAME : square.opt.tour\nTYPE : TOUR\nDIMENSION : 4\nTOUR_SECTION\n1\n4\n3\n2\n-1\nEOF\n", 4).unwrap();
+		assert_eq!(tour, vec![0, 3, 2, 1]);
+		assert!(parse_tour("TOUR_SECTION\n1 2 2 3\n-1\n", 4).is_err());
+		assert!(parse_tour("TOUR_SECTION\n1 2 3\n-1\n", 4).is_err());
+
+		let att = Tsp::parse(&text.replace("EUC_2D", "ATT")).unwrap();
+		assert_eq!(att.distance(0, 2), 2.0);
+		assert!(Tsp::parse(&text.replace("EUC_2D", "EUC_3D")).is_err());
+		assert!(Tsp::parse(&text.replace("TYPE : TSP", "TYPE : CVRP")).is_err());
+	}
+
+	#[test]
+	fn explicit_test() {
+		let full = Tsp::parse("NAME: full\nTYPE: ATSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: FULL_MATRIX\nEDGE_WEIGHT_SECTION\n0 1 2\n3 0 4\n5 6 0\nEOF").unwrap();
+		assert_eq!(full.distance(1, 0), 3.0);
+		assert_eq!(full.tour_length(&[0, 1, 2]), 10.0);
+
+		let upper = Tsp::parse("DIMENSION: 4\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\nEDGE_WEIGHT_SECTION\n1 2 3\n4 5\n6\n").unwrap();
+		let lower = Tsp::parse("DIMENSION: 4\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: LOWER_DIAG_ROW\nEDGE_WEIGHT_SECTION\n0 1 0 2 4 0\n3 5 6 0\n").unwrap();
+		assert_eq!(upper.distance(1, 3), 5.0);
+		assert_eq!(upper.distance(3, 1), 5.0);
+		assert_eq!(upper, lower);
+		assert!(Tsp::parse("DIMENSION: 4\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\nEDGE_WEIGHT_SECTION\n1 2 3\n").is_err());
+	}
+
+	#[test]
+	fn geo_test() {
+		// Berlin to Paris, about 880 km
+		let tsp = Tsp::parse("DIMENSION: 2\nEDGE_WEIGHT_TYPE: GEO\nNODE_COORD_SECTION\n1 52.31 13.24\n2 48.52 2.21\nEOF").unwrap();
+		assert!((870.0..890.0).contains(&tsp.distance(0, 1)), "distance is {}", tsp.distance(0, 1));
+		assert_eq!(tsp.distance(0, 0), 0.0);
+	}
+}
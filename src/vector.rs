@@ -1,5 +1,36 @@
+use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 
+use crate::bounds::Bounds;
+
+/// Point in the search space, implemented by fixed size `VectorN` and heap allocated `DynVector`
+pub trait Vector: Clone + Debug + PartialEq + Send + Sync + 'static
+	+ Add<Output = Self> + Add<f64, Output = Self> + AddAssign + AddAssign<f64>
+	+ Sub<Output = Self> + SubAssign
+	+ Mul<Output = Self> + Mul<f64, Output = Self> + MulAssign<f64>
+	+ Div<f64, Output = Self>
+{
+	/// Creates a vector with all coordinates set to zero. Fixed size vectors ignore the dimension count
+	fn zeros(dimensions: usize) -> Self;
+
+	fn as_slice(&self) -> &[f64];
+
+	fn as_mut_slice(&mut self) -> &mut [f64];
+
+	fn dimensions(&self) -> usize {
+		return self.as_slice().len();
+	}
+
+	/// Moves every coordinate into its range given by the bounds
+	fn clamp(&mut self, bounds: &Bounds<Self>) {
+		let lower = bounds.lower.as_slice();
+		let upper = bounds.upper.as_slice();
+		for (index, a) in self.as_mut_slice().iter_mut().enumerate() {
+			*a = a.clamp(lower[index], upper[index]);
+		}
+	}
+}
+
 #[derive(Clone, Debug, Copy, PartialEq)]
 pub struct VectorN<const N: usize> {
 	pub coordinates: [f64; N],
@@ -11,10 +42,19 @@ impl<const N: usize> VectorN<N> {
 			coordinates
 		};
 	}
-	pub fn clamp(&mut self, bounds: (f64, f64)) {
-		for a in &mut self.coordinates {
-			*a = a.clamp(bounds.0, bounds.1);
-		}
+}
+
+impl<const N: usize> Vector for VectorN<N> {
+	fn zeros(_dimensions: usize) -> Self {
+		return Self::default();
+	}
+
+	fn as_slice(&self) -> &[f64] {
+		return &self.coordinates;
+	}
+
+	fn as_mut_slice(&mut self) -> &mut [f64] {
+		return &mut self.coordinates;
 	}
 }
 
@@ -117,12 +157,129 @@ impl<const N: usize> Default for VectorN<N> {
 	}
 }
 
+/// Vector stored on the heap, for dimension counts without a `VectorN` instantiation
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynVector {
+	pub coordinates: Vec<f64>,
+}
+
+impl DynVector {
+	pub fn new(coordinates: Vec<f64>) -> Self {
+		return Self {
+			coordinates
+		};
+	}
+}
+
+impl Vector for DynVector {
+	fn zeros(dimensions: usize) -> Self {
+		return Self {
+			coordinates: vec![0.0; dimensions],
+		};
+	}
+
+	fn as_slice(&self) -> &[f64] {
+		return &self.coordinates;
+	}
+
+	fn as_mut_slice(&mut self) -> &mut [f64] {
+		return &mut self.coordinates;
+	}
+}
+
+impl Add<f64> for DynVector {
+	type Output = DynVector;
+	fn add(mut self, rhs: f64) -> Self::Output {
+		self += rhs;
+		return self;
+	}
+}
+
+impl Mul<f64> for DynVector {
+	type Output = DynVector;
+	fn mul(mut self, rhs: f64) -> Self::Output {
+		self *= rhs;
+		return self;
+	}
+}
+
+impl MulAssign<f64> for DynVector {
+	fn mul_assign(&mut self, rhs: f64) {
+		for entry in &mut self.coordinates {
+			*entry *= rhs;
+		}
+	}
+}
+
+impl Div<f64> for DynVector {
+	type Output = DynVector;
+	fn div(mut self, rhs: f64) -> Self::Output {
+		for entry in &mut self.coordinates {
+			*entry /= rhs;
+		}
+		return self;
+	}
+}
+
+impl Mul for DynVector {
+	type Output = DynVector;
+	fn mul(mut self, rhs: Self) -> Self::Output {
+		debug_assert_eq!(self.coordinates.len(), rhs.coordinates.len());
+		for (entry, other) in self.coordinates.iter_mut().zip(rhs.coordinates) {
+			*entry *= other;
+		}
+		return self;
+	}
+}
+
+impl Sub for DynVector {
+	type Output = DynVector;
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self -= rhs;
+		return self;
+	}
+}
+
+impl SubAssign for DynVector {
+	fn sub_assign(&mut self, rhs: Self) {
+		debug_assert_eq!(self.coordinates.len(), rhs.coordinates.len());
+		for (entry, other) in self.coordinates.iter_mut().zip(rhs.coordinates) {
+			*entry -= other;
+		}
+	}
+}
+
+impl Add for DynVector {
+	type Output = DynVector;
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self += rhs;
+		return self;
+	}
+}
+
+impl AddAssign for DynVector {
+	fn add_assign(&mut self, rhs: Self) {
+		debug_assert_eq!(self.coordinates.len(), rhs.coordinates.len());
+		for (entry, other) in self.coordinates.iter_mut().zip(rhs.coordinates) {
+			*entry += other;
+		}
+	}
+}
+
+impl AddAssign<f64> for DynVector {
+	fn add_assign(&mut self, rhs: f64) {
+		for entry in &mut self.coordinates {
+			*entry += rhs;
+		}
+	}
+}
+
 pub trait QuickFold {
 	fn sum(&self) -> f64;
 	fn product(&self) -> f64;
 }
 
-impl<const N: usize> QuickFold for [f64; N] {
+impl QuickFold for [f64] {
 	fn sum(&self) -> f64 {
 		let mut result = 0.0;
 		for entry in self {
@@ -140,8 +297,10 @@ impl<const N: usize> QuickFold for [f64; N] {
 }
 
 #[cfg(test)]
+// the operator tests clone the vectors as they would the heap allocated ones
+#[allow(clippy::clone_on_copy)]
 mod test {
-    use crate::vector::{QuickFold, VectorN};
+    use crate::{bounds::Bounds, vector::{DynVector, QuickFold, Vector, VectorN}};
 
 	#[test]
 	fn add_test() {
@@ -214,9 +373,14 @@ mod test {
 		let mut a = VectorN::<_> {
 			coordinates: [1.0, 2.0, 3.0]
 		};
-		a.clamp((1.5, 2.5));
+		a.clamp(&Bounds::uniform(3, 1.5, 2.5));
 
 		assert_eq!(a.coordinates, [1.5, 2.0, 2.5]);
+
+		let mut b = VectorN::new([2.0, 2.0, 0.25]);
+		b.clamp(&Bounds::new(VectorN::new([-1.0, 10.0, 0.0]), VectorN::new([1.0, 20.0, 0.5])));
+
+		assert_eq!(b.coordinates, [1.0, 10.0, 0.25]);
 	}
 	#[test]
 	fn sum_test() {
@@ -229,4 +393,17 @@ mod test {
 		let a = [2.0, 3.0, 4.0];
 		assert_eq!(24.0, a.product());
 	}
+
+	#[test]
+	fn dyn_vector_test() {
+		let a = DynVector::new(vec![1.0, 2.0, 3.0]);
+		let b = DynVector::new(vec![1.0, 2.0, 3.0]);
+
+		assert_eq!((a.clone() + b.clone()).coordinates, [2.0, 4.0, 6.0]);
+		assert_eq!((a.clone() - b.clone()).coordinates, [0.0, 0.0, 0.0]);
+		assert_eq!((a.clone() * b).coordinates, [1.0, 4.0, 9.0]);
+		assert_eq!((a.clone() * 2.0).coordinates, [2.0, 4.0, 6.0]);
+		assert_eq!((a / 2.0).coordinates, [0.5, 1.0, 1.5]);
+		assert_eq!(DynVector::zeros(4).dimensions(), 4);
+	}
 }
\ No newline at end of file
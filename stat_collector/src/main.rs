@@ -1,8 +1,13 @@
+#![allow(clippy::needless_return)]
+
 fn main() {
-	let header = "z_parameter,migration_threshold,fn_name,max_solution,avg_solution,min_solution";
+	let header = "z_parameter,migration_threshold,fn_name,dimensions,max_solution,avg_solution,min_solution,avg_error,avg_distance,success_rate,feasibility_rate";
 
 	let filename_gex = regex::Regex::new(r".*_(\d*\.\d*)_.*_(\d*\.\d*)").unwrap();
-	let gex = regex::Regex::new(r"(.*): .*is (-?\d*(?:\.\d*)?)\..*is (-?\d*(?:\.\d*)?)\. .*is (-?\d*(?:\.\d*)?)").unwrap();
+	let gex = regex::Regex::new(concat!(
+		r"(.*) \((\d+)D\): .*Max solution is (\S+)\. Average solution is (\S+)\. Min solution is (\S+)\. ",
+		r"Average error is (\S+)\. Average distance to optimum is (\S+)\. Success rate is (\S+)\. Feasibility rate is (\S+)\."
+	)).unwrap();
 
 	println!("{}", header);
 
@@ -11,7 +16,10 @@ fn main() {
 		let filename_captures = filename_gex.captures(filename.to_str().unwrap()).unwrap();
 		let stat_data = std::fs::read_to_string(&filename).unwrap().lines().map(|line| {
 			let captures = gex.captures(line).unwrap();
-			return format!("{},{},{},{},{},{}", &filename_captures[2], &filename_captures[1], &captures[1], &captures[2], &captures[3], &captures[4]);
+			return format!(
+				"{},{},{},{},{},{},{},{},{},{},{}", &filename_captures[2], &filename_captures[1],
+				&captures[1], &captures[2], &captures[3], &captures[4], &captures[5], &captures[6], &captures[7], &captures[8], &captures[9]
+			);
 		}).collect::<Vec<_>>().join("\n");
 		println!("{}", stat_data);
 	}